@@ -1,8 +1,12 @@
+// systems take each resource and query they use as an argument
+#![allow(clippy::too_many_arguments)]
+
 use crate::prelude::*;
 use bevy_ecs::{IntoSystem, Stage};
 
 mod model;
 mod systems;
+mod validate;
 
 mod prelude {
     pub use crate::model::*;
@@ -13,16 +17,12 @@ mod prelude {
     pub use bevy_ecs::prelude as bevy;
     pub use bevy_ecs::prelude::*;
 
-    pub use legion::systems::CommandBuffer;
     pub use legion::systems::*;
-    pub use legion::world::SubWorld;
 
     pub use itertools::Itertools;
-    pub use itertools_num;
 }
 
 struct State {
-    pub title: String,
     world: bevy::World,
     resources: bevy::Resources,
     schedule: bevy::Schedule,
@@ -32,6 +32,8 @@ struct State {
 pub enum AppState {
     Menu,
     Playing,
+    Editor,
+    RoundComplete,
 }
 
 impl State {
@@ -40,6 +42,7 @@ impl State {
         let mut state_stage = StateStage::<AppState>::default();
         state_stage.on_state_update(AppState::Menu, menu_system::menu.system());
         state_stage.on_state_update(AppState::Playing, map_render::map_render.system());
+        state_stage.on_state_update(AppState::Playing, computer_player::computer_player.system());
         state_stage.on_state_update(
             AppState::Playing,
             hole_handler::hole_handler
@@ -47,44 +50,73 @@ impl State {
                 .chain(hole_handler::hole_transition.system()),
         );
         state_stage.on_state_update(AppState::Playing, turn_handler::turn_handler.system());
+        state_stage.on_state_update(AppState::Playing, turn_order::turn_order.system());
         state_stage.on_state_update(AppState::Playing, ball_render::ball_render.system());
         state_stage.on_state_update(AppState::Playing, ui_render::render_ui.system());
+        state_stage.on_state_update(
+            AppState::Playing,
+            scorecard_render::scorecard_overlay.system(),
+        );
+        state_stage.on_state_update(
+            AppState::RoundComplete,
+            scorecard_render::round_summary.system(),
+        );
+        state_stage.on_state_update(AppState::Editor, map_render::map_render.system());
+        state_stage.on_state_update(AppState::Editor, editor_system::editor.system());
         schedule.add_stage("main", state_stage);
         schedule
     }
 
-    fn new() -> Self {
+    fn new(course: Result<Course, CourseError>, clubs: ClubSet) -> Self {
         let mut world: bevy::World = Default::default();
         let mut resources: bevy::Resources = Default::default();
         let mut schedule: bevy::Schedule = State::build_schedule();
 
         let window = Window::new();
-        let mut course = Course::default();
-        let hole = course.next().unwrap();
-        let map = &hole.map;
-        let ball = Ball::new(&map.tee);
-        let cam = Camera::new(
-            ball.tile_position(),
-            map.width as i32,
-            map.height as i32,
-            window.width as i32 - 15,
-            window.height as i32 - 10,
-        );
+        match course {
+            Ok(mut course) => {
+                let setup = Setup::new(course.tee_names());
+                let mut hole = course.current().unwrap();
+                hole.place(&setup);
+                let map = &hole.map;
+                let ball = Ball::new(&map.tee);
+                let cam = Camera::new(
+                    ball.tile_position(),
+                    map.width as i32,
+                    map.height as i32,
+                    window.width as i32 - 15,
+                    window.height as i32 - 10,
+                );
+                resources.insert(cam);
+                resources.insert(CourseStatus::Ready(course.name.clone()));
+                resources.insert(course);
+                resources.insert(setup);
+                resources.insert(Editor::new(&hole));
+                resources.insert(hole);
+                world.spawn((ball, Player::new(0, clubs.clone()), Active));
+            }
+            Err(error) => {
+                println!("Could not load course: {}", error);
+                resources.insert(CourseStatus::Failed(error));
+                resources.insert(Course::empty());
+                resources.insert(Setup::new(Vec::new()));
+            }
+        }
 
-        resources.insert(cam);
         resources.insert(FrameTime::new());
         resources.insert(bevy::State::new(AppState::Menu));
-        resources.insert(course);
-        resources.insert(hole);
+        resources.insert(Wind::random());
+        resources.insert(clubs);
         resources.insert(TurnStage::start());
         resources.insert(HoleState::new());
+        resources.insert(Scorecard::new());
+        resources.insert(Format::StrokePlay.scoring(1, None, &[]));
+        resources.insert(Notice::new());
         resources.insert(window);
-        world.spawn((ball,));
 
         schedule.initialize(&mut world, &mut resources);
 
         Self {
-            title: "ON TOUR!".to_string(),
             world,
             resources,
             schedule,
@@ -102,7 +134,83 @@ impl GameState for State {
     }
 }
 
+/** `golf generate <seed> <par> [straight|left|right] [fairway width] [hazard density]`
+ * prints a generated hole in the map format. **/
+fn generate(args: &[String]) -> bool {
+    let number = |i: usize| args.get(i).and_then(|a| a.parse::<f32>().ok());
+    let (seed, par) = match (args.first().and_then(|a| a.parse().ok()), number(1)) {
+        (Some(seed), Some(par)) => (seed, par as u32),
+        _ => {
+            println!("usage: generate <seed> <par> [straight|left|right] [width] [density]");
+            return false;
+        }
+    };
+    let mut spec = HoleSpec::new(seed, par);
+    if let Some(dogleg) = args.get(2).and_then(|a| Dogleg::from_name(a)) {
+        spec.dogleg = dogleg;
+    }
+    if let Some(width) = number(3) {
+        spec.fairway_width = width as i32;
+    }
+    if let Some(density) = number(4) {
+        spec.hazard_density = density;
+    }
+    let clubs = match ClubSet::load(ClubSet::DEFAULT_FILE) {
+        Ok(clubs) => clubs,
+        Err(error) => {
+            println!("{}: {}", ClubSet::DEFAULT_FILE, error);
+            return false;
+        }
+    };
+    for line in spec.generate(&clubs).to_lines() {
+        println!("{}", line);
+    }
+    true
+}
+
+/** The clubs and the course to play, generated from `practice` if there is
+ * a seed. Whichever fails to load is reported on the menu. **/
+fn load(practice: Option<u64>) -> (ClubSet, Result<Course, CourseError>) {
+    match ClubSet::load(ClubSet::DEFAULT_FILE) {
+        Ok(clubs) => {
+            let course = match practice {
+                Some(seed) => Ok(Course::practice(seed, clubs.clone())),
+                None => Course::load(Course::DEFAULT_FILE),
+            };
+            (clubs, course)
+        }
+        Err(error) => (ClubSet::empty(), Err(CourseError::Clubs(error))),
+    }
+}
+
 fn main() -> BError {
+    // `golf validate <map or course>...` checks files instead of playing
+    let args: Vec<String> = std::env::args().collect();
+    let practice = match args.get(1).map(String::as_str) {
+        Some("validate") => {
+            if args.len() < 3 {
+                println!("usage: validate <map or course>...");
+                std::process::exit(1);
+            }
+            let failed = args[2..]
+                .iter()
+                .filter(|path| !validate::validate(path))
+                .count();
+            std::process::exit(if failed == 0 { 0 } else { 1 });
+        }
+        Some("generate") => std::process::exit(if generate(&args[2..]) { 0 } else { 1 }),
+        // `golf practice [seed]` plays endless generated holes
+        Some("practice") => {
+            let seed = args
+                .get(2)
+                .and_then(|a| a.parse().ok())
+                .unwrap_or_else(|| RandomNumberGenerator::new().range(0, 100000) as u64);
+            Some(seed)
+        }
+        _ => None,
+    };
+    let (clubs, course) = load(practice);
+
     let context = BTermBuilder::default()
         .with_dimensions(80, 60)
         .with_font("terminal8x8.png".to_string(), 8, 8)
@@ -111,7 +219,7 @@ fn main() -> BError {
         .with_fps_cap(30.0)
         .build()?;
 
-    let s = State::new();
+    let s = State::new(course, clubs);
 
     main_loop(context, s)
 }
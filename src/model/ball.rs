@@ -4,6 +4,9 @@ use crate::prelude::*;
 pub struct Ball {
     x: f32,
     y: f32,
+    // where the last shot was played from
+    lie_x: f32,
+    lie_y: f32,
 }
 
 impl Ball {
@@ -11,17 +14,35 @@ impl Ball {
         Self {
             x: position.x as f32,
             y: position.y as f32,
+            lie_x: position.x as f32,
+            lie_y: position.y as f32,
         }
     }
 
     pub fn tile_position(&self) -> Point {
-        Point::new(self.x as i32, self.y as i32)
+        Point::new(self.x.floor() as i32, self.y.floor() as i32)
+    }
+
+    /** Remember the spot a shot is played from. **/
+    pub fn strike(&mut self) {
+        self.lie_x = self.x;
+        self.lie_y = self.y;
+    }
+
+    pub fn lie_position(&self) -> Point {
+        Point::new(self.lie_x.floor() as i32, self.lie_y.floor() as i32)
+    }
+
+    /** Put the ball back where the last shot was played from. **/
+    pub fn replay(&mut self) {
+        self.x = self.lie_x;
+        self.y = self.lie_y;
     }
 
     pub fn mv(&mut self, deg: f32, dist: f32) {
         let rads = (deg + 90.).to_radians();
         let dx = rads.cos() * dist;
-        let dy = -1. * rads.sin() * dist;
+        let dy = -rads.sin() * dist;
         self.x += dx;
         self.y += dy;
     }
@@ -29,5 +50,25 @@ impl Ball {
     pub fn move_to(&mut self, destination: &Point) {
         self.x = destination.x as f32;
         self.y = destination.y as f32;
+        self.strike();
+    }
+
+    /** How far the ball lies from the centre of `tile`, in tiles. **/
+    pub fn distance_to(&self, tile: &Point) -> f32 {
+        self.closest_approach(self, tile)
+    }
+
+    /** Closest the ball came to the centre of `tile` on its way here from `from`. **/
+    pub fn closest_approach(&self, from: &Ball, tile: &Point) -> f32 {
+        let (cx, cy) = (tile.x as f32 + 0.5, tile.y as f32 + 0.5);
+        let (dx, dy) = (self.x - from.x, self.y - from.y);
+        let length = dx.powf(2.) + dy.powf(2.);
+        let t = if length > 0. {
+            (((cx - from.x) * dx + (cy - from.y) * dy) / length).clamp(0., 1.)
+        } else {
+            0.
+        };
+        let (px, py) = (from.x + t * dx, from.y + t * dy);
+        ((cx - px).powf(2.) + (cy - py).powf(2.)).sqrt()
     }
 }
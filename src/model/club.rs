@@ -1,32 +1,103 @@
-#[derive(Copy, Clone, Debug)]
+use crate::prelude::*;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::*;
+
+#[derive(Clone, Debug)]
 pub struct Club {
-    id: u32,
-    pub name: &'static str,
+    pub name: String,
+    pub short_name: String,
     pub loft_deg: f32,
     pub max_initial_velocity: f32,
+    pub spin_rpm: f32,
 }
 
 impl Club {
-    const DRIVER: Club = Club {
-        id: 1,
-        name: "Driver",
-        loft_deg: 12.,
-        max_initial_velocity: 73.76,
-    };
-    const PUTTER: Club = Club {
-        id: 2,
-        name: "Putter",
-        loft_deg: 0.,
-        max_initial_velocity: 1.,
-    };
+    /** Parse a `name, short name, loft, max velocity, spin` line. **/
+    fn parse(line: &str) -> Option<Club> {
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        match fields.as_slice() {
+            [name, short_name, loft, velocity, spin] => Some(Club {
+                name: name.to_string(),
+                short_name: short_name.to_string(),
+                loft_deg: loft.parse().ok()?,
+                max_initial_velocity: velocity.parse().ok()?,
+                spin_rpm: spin.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ClubError {
+    Io(io::Error),
+    BadLine { line: usize, text: String },
+    TooMany(usize),
+    Empty,
+}
+
+impl fmt::Display for ClubError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClubError::Io(e) => write!(f, "could not read clubs: {}", e),
+            ClubError::BadLine { line, text } => {
+                write!(f, "clubs line {} is not a club: {}", line, text)
+            }
+            ClubError::TooMany(count) => write!(
+                f,
+                "{} clubs in the bag, at most {} allowed",
+                count,
+                ClubSet::MAX_CLUBS
+            ),
+            ClubError::Empty => write!(f, "the bag has no clubs"),
+        }
+    }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct ClubSet {
-    clubs: [Club; 2],
+    clubs: Vec<Club>,
 }
 
 impl ClubSet {
+    pub const MAX_CLUBS: usize = 14;
+
+    pub const DEFAULT_FILE: &'static str = "src/clubs.txt";
+
+    pub fn load(filename: &str) -> std::result::Result<ClubSet, ClubError> {
+        let f: File = File::open(filename).map_err(ClubError::Io)?;
+        let mut clubs = Vec::new();
+        for (number, line) in BufReader::new(f).lines().enumerate() {
+            let line = line.map_err(ClubError::Io)?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            clubs.push(Club::parse(line).ok_or_else(|| ClubError::BadLine {
+                line: number + 1,
+                text: line.to_string(),
+            })?);
+        }
+        if clubs.len() > ClubSet::MAX_CLUBS {
+            Err(ClubError::TooMany(clubs.len()))
+        } else if clubs.is_empty() {
+            Err(ClubError::Empty)
+        } else {
+            Ok(ClubSet { clubs })
+        }
+    }
+
+    /** A bag with nothing in it, to stand in when the clubs failed to load. **/
+    pub fn empty() -> ClubSet {
+        ClubSet { clubs: Vec::new() }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Club> {
+        self.clubs.iter()
+    }
+
     pub fn previous_club(&self, selected: usize) -> usize {
         if selected == 0 {
             self.clubs.len() - 1
@@ -39,13 +110,55 @@ impl ClubSet {
         self.clubs.get(selected + 1).map_or(0, |_| selected + 1)
     }
 
-    pub fn at(&self, selection: &usize) -> Club {
-        self.clubs[*selection]
+    /** The club to start a turn with: the putter on a green, otherwise the top of the bag. **/
+    pub fn default_for(&self, lie: &Surface) -> usize {
+        let putter = self.clubs.iter().position(|c| c.loft_deg == 0.);
+        match (lie.stimp, putter) {
+            (Some(_), Some(putter)) => putter,
+            _ => 0,
+        }
+    }
+
+    pub fn at(&self, selection: &usize) -> &Club {
+        &self.clubs[*selection]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_text(name: &str, text: &str) -> std::result::Result<ClubSet, ClubError> {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, text).unwrap();
+        ClubSet::load(path.to_str().unwrap())
+    }
+
+    #[test]
+    fn the_default_bag_is_full() {
+        let clubs = ClubSet::load(ClubSet::DEFAULT_FILE).unwrap();
+        assert_eq!(clubs.iter().count(), ClubSet::MAX_CLUBS);
+        assert!(clubs.iter().any(|c| c.short_name == "3i"));
+        assert!(clubs.iter().any(|c| c.short_name == "Pt"));
+    }
+
+    #[test]
+    fn a_bad_line_is_named() {
+        match load_text(
+            "bad_club.txt",
+            "# clubs\nDriver, Dr, 12, 73.76, 3275\nPutter, Pt, 0\n",
+        ) {
+            Err(ClubError::BadLine { line, .. }) => assert_eq!(line, 3),
+            other => panic!("expected a bad line, got {:?}", other),
+        }
     }
 
-    pub fn default() -> ClubSet {
-        ClubSet {
-            clubs: [Club::DRIVER, Club::PUTTER],
+    #[test]
+    fn too_many_clubs_is_an_error() {
+        let text = "7 Iron, 7i, 23, 35.27, 7100\n".repeat(ClubSet::MAX_CLUBS + 1);
+        match load_text("too_many_clubs.txt", &text) {
+            Err(ClubError::TooMany(count)) => assert_eq!(count, ClubSet::MAX_CLUBS + 1),
+            other => panic!("expected too many clubs, got {:?}", other),
         }
     }
 }
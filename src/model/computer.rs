@@ -0,0 +1,106 @@
+use crate::prelude::*;
+
+/** A golfer the computer plays for. It plans each shot, then works the same
+ * keys a person would, with a hand as steady as its `skill` (0 to 1). **/
+pub struct Computer {
+    pub skill: f32,
+    // the shot it means to play, with its own error already in it
+    shot: Option<Shot>,
+    // seconds before it touches a key, so its play can be followed
+    wait: f32,
+}
+
+impl Computer {
+    pub const SKILLS: [f32; Player::MAX_PLAYERS] = [0.9, 0.8, 0.7, 0.6];
+    const PAUSE: f32 = 0.8;
+    // meter points a golfer of no skill at all may be off by
+    const POWER_ERROR: f32 = 12.;
+    const ACCURACY_ERROR: f32 = 10.;
+
+    pub fn new(skill: f32) -> Self {
+        Computer {
+            skill,
+            shot: None,
+            wait: 0.,
+        }
+    }
+
+    /** The key to press this frame, at `stage` of a turn played from `ball`. **/
+    pub fn press(
+        &mut self,
+        dt: f32,
+        stage: &TurnStage,
+        ball: &Ball,
+        map: &Map,
+        clubs: &ClubSet,
+        wind: &Wind,
+    ) -> Option<VirtualKeyCode> {
+        if self.wait > 0. {
+            self.wait -= dt;
+            return None;
+        }
+        match (*stage, self.shot) {
+            (TurnStage::ClubSelection(_), None) => {
+                let shot = Planner::new(map, clubs, wind).plan(ball);
+                self.shot = Some(self.miss(shot));
+                self.wait = Computer::PAUSE;
+                None
+            }
+            (TurnStage::ClubSelection(club), Some(shot)) if club != shot.club => {
+                Some(VirtualKeyCode::Down)
+            }
+            (TurnStage::Aiming(aim, _), Some(shot)) => {
+                let off = shot.aim.degrees - aim.degrees;
+                if off > Aim::RATE / 2. {
+                    Some(VirtualKeyCode::Left)
+                } else if off < -Aim::RATE / 2. {
+                    Some(VirtualKeyCode::Right)
+                } else {
+                    Some(VirtualKeyCode::Space)
+                }
+            }
+            // stop each meter on the frame it would pass the mark
+            (TurnStage::Swinging(Swing::Power(power), _, _), Some(shot)) => {
+                let pos = ball.tile_position();
+                let putting = map.in_bounds(&pos) && map.tile_at(&pos).surface().stimp.is_some();
+                let rate = if putting {
+                    Swing::PUTT_POWER_RATE
+                } else {
+                    Swing::POWER_RATE
+                };
+                if power + rate >= shot.power {
+                    Some(VirtualKeyCode::Space)
+                } else {
+                    None
+                }
+            }
+            (TurnStage::Swinging(Swing::Accuracy(_, accuracy), _, _), Some(shot)) => {
+                if accuracy - Swing::RETURN_RATE <= shot.accuracy {
+                    Some(VirtualKeyCode::Space)
+                } else {
+                    None
+                }
+            }
+            (TurnStage::Traveling(_), _) => None,
+            (TurnStage::Finished, Some(_)) => {
+                // watch where it went before moving on
+                self.shot = None;
+                self.wait = Computer::PAUSE;
+                None
+            }
+            _ => Some(VirtualKeyCode::Space),
+        }
+    }
+
+    // the shot as this golfer will actually strike it
+    fn miss(&self, shot: Shot) -> Shot {
+        let mut rng = RandomNumberGenerator::new();
+        let spread = 1. - self.skill;
+        let power = shot.power + rng.range::<f32>(-1., 1.) * spread * Computer::POWER_ERROR;
+        Shot {
+            power: power.clamp(1., 100.),
+            accuracy: shot.accuracy + rng.range::<f32>(-1., 1.) * spread * Computer::ACCURACY_ERROR,
+            ..shot
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dogleg {
+    Straight,
+    Left,
+    Right,
+}
+
+impl Dogleg {
+    pub fn from_name(name: &str) -> Option<Dogleg> {
+        match name {
+            "straight" => Some(Dogleg::Straight),
+            "left" => Some(Dogleg::Left),
+            "right" => Some(Dogleg::Right),
+            _ => None,
+        }
+    }
+
+    // which way the second leg bends across the map
+    fn sign(self) -> f32 {
+        match self {
+            Dogleg::Straight => 0.,
+            Dogleg::Left => -1.,
+            Dogleg::Right => 1.,
+        }
+    }
+}
+
+/** What a generated hole should look like; the seed decides the rest. **/
+#[derive(Debug, Clone)]
+pub struct HoleSpec {
+    pub seed: u64,
+    pub par: u32,
+    pub dogleg: Dogleg,
+    /// tiles across the fairway
+    pub fairway_width: i32,
+    /// 0 for a clean hole, 1 for one crowded with sand, water and trees
+    pub hazard_density: f32,
+}
+
+/** An endless run of generated holes. **/
+pub struct Practice {
+    seed: u64,
+    played: usize,
+    clubs: ClubSet,
+}
+
+impl Practice {
+    pub fn new(seed: u64, clubs: ClubSet) -> Self {
+        Practice {
+            seed,
+            played: 0,
+            clubs,
+        }
+    }
+
+    pub fn next_hole(&mut self) -> Hole {
+        let spec = HoleSpec::random(self.seed);
+        self.seed = self.seed.wrapping_add(1);
+        self.played += 1;
+        Hole::generated(self.played, &spec, &self.clubs)
+    }
+}
+
+type Spot = (f32, f32);
+
+impl HoleSpec {
+    const WIDTH: usize = 80;
+    const GREEN_RADIUS: f32 = 3.5;
+    const BEND_DEG: f32 = 35.;
+    // rows left above the green and below the tee
+    const TOP_MARGIN: f32 = 8.;
+    const BOTTOM_MARGIN: f32 = 5.;
+    // no leg is laid out longer than this share of the longest full shot
+    const SAFE_SHARE: f32 = 0.85;
+    // the fairway starts past this share of the longest shot, off the tee
+    const CARRY_SHARE: f32 = 0.35;
+
+    pub fn new(seed: u64, par: u32) -> Self {
+        HoleSpec {
+            seed,
+            par,
+            dogleg: Dogleg::Straight,
+            fairway_width: 7,
+            hazard_density: 0.3,
+        }
+    }
+
+    /** Everything picked from the seed, for practice. **/
+    pub fn random(seed: u64) -> Self {
+        let mut rng = RandomNumberGenerator::seeded(seed);
+        let par = rng.range(3, 6) as u32;
+        let dogleg = match rng.range(0, 3) {
+            0 => Dogleg::Left,
+            1 => Dogleg::Right,
+            _ => Dogleg::Straight,
+        };
+        HoleSpec {
+            seed,
+            par,
+            dogleg,
+            fairway_width: rng.range(5, 10),
+            hazard_density: rng.range(0.1, 0.7),
+        }
+    }
+
+    /** Lay out the hole, thinning out hazards until the flag can be reached
+     * with `clubs`. **/
+    pub fn generate(&self, clubs: &ClubSet) -> Map {
+        let reach = clubs
+            .iter()
+            .map(|club| Travel::full_distance(club, &Surface::TEE_BOX))
+            .fold(0., f32::max);
+        let mut density = self.hazard_density;
+        loop {
+            let map = self.build(reach, density);
+            // with no hazards every tile is playable and every leg within reach
+            if density <= 0. || map.flag_reachable(clubs) {
+                return map;
+            }
+            density = if density > 0.05 { density / 2. } else { 0. };
+        }
+    }
+
+    fn build(&self, reach: f32, density: f32) -> Map {
+        let mut rng = RandomNumberGenerator::seeded(self.seed);
+        let safe = reach * HoleSpec::SAFE_SHARE;
+        let shots = self.par.clamp(3, 5) - 2;
+        let length = safe * rng.range(shots as f32 - 0.4, shots as f32);
+
+        // tee, the corner of the dogleg, and the middle of the green
+        let bend = if shots > 1 {
+            self.dogleg.sign() * HoleSpec::BEND_DEG.to_radians()
+        } else {
+            0.
+        };
+        let first = if shots > 1 {
+            safe.min(length * 0.6)
+        } else {
+            length
+        };
+        let second = length - first;
+        let rise = first + second * bend.cos();
+        let run = second * bend.sin();
+        let width = HoleSpec::WIDTH;
+        let height = (rise + HoleSpec::TOP_MARGIN + HoleSpec::BOTTOM_MARGIN).ceil() as usize;
+        let tee: Spot = (
+            (width as f32 / 2. - run / 2.).round(),
+            height as f32 - HoleSpec::BOTTOM_MARGIN,
+        );
+        let corner: Spot = (tee.0, tee.1 - first);
+        let green: Spot = (corner.0 + run, corner.1 - second * bend.cos());
+
+        let mut tiles = vec![MapTile::DeepRough; width * height];
+        let spots: Vec<Spot> = (0..width * height)
+            .map(|i| ((i % width) as f32, (i / width) as f32))
+            .collect();
+        let half = self.fairway_width as f32 / 2.;
+        let wobble = rng.range(0., std::f32::consts::PI * 2.);
+        for (i, spot) in spots.iter().enumerate() {
+            let along = distance(*spot, tee);
+            let off_line =
+                segment_distance(*spot, tee, corner).min(segment_distance(*spot, corner, green));
+            let edge = half + (spot.1 * 0.3 + wobble).sin() * 0.8;
+            if shots > 1 && off_line <= edge && along >= reach * HoleSpec::CARRY_SHARE {
+                tiles[i] = MapTile::Fairway;
+            }
+            if distance(*spot, green) <= HoleSpec::GREEN_RADIUS {
+                tiles[i] = MapTile::Green;
+            }
+        }
+
+        // sand around the green and beside the landing area
+        let bunkers = (density * 5.).round() as usize;
+        for n in 0..bunkers {
+            let center = if n % 2 == 0 || shots == 1 {
+                let angle = rng.range(0., std::f32::consts::PI * 2.);
+                let gap = HoleSpec::GREEN_RADIUS + 1.5;
+                (green.0 + angle.cos() * gap, green.1 + angle.sin() * gap)
+            } else {
+                let side = if rng.range(0, 2) == 0 { -1. } else { 1. };
+                let y = corner.1 + rng.range(-2., 4.);
+                (corner.0 + side * (half + 1.), y)
+            };
+            let radius = rng.range(1., 2.);
+            paint(
+                &mut tiles,
+                &spots,
+                center,
+                radius,
+                MapTile::Bunker,
+                |tile| matches!(tile, MapTile::DeepRough | MapTile::Fairway),
+            );
+        }
+
+        // a pond short of a par three, or alongside the second leg
+        if rng.range(0., 1.) < density {
+            let side = if rng.range(0, 2) == 0 { -1. } else { 1. };
+            let center = if shots == 1 {
+                (
+                    tee.0 + (green.0 - tee.0) * 0.55 + rng.range(-3., 3.),
+                    tee.1 + (green.1 - tee.1) * 0.55,
+                )
+            } else {
+                let middle = ((corner.0 + green.0) / 2., (corner.1 + green.1) / 2.);
+                let across = (bend.cos(), bend.sin());
+                (
+                    middle.0 + side * across.0 * (half + 4.),
+                    middle.1 + side * across.1 * (half + 4.),
+                )
+            };
+            let radius = rng.range(2.5, 4.);
+            paint(&mut tiles, &spots, center, radius, MapTile::Water, |tile| {
+                matches!(tile, MapTile::DeepRough)
+            });
+        }
+
+        for tile in tiles.iter_mut() {
+            if let MapTile::DeepRough = tile {
+                let roll = rng.range(0., 1.);
+                if roll < density * 0.06 {
+                    *tile = MapTile::Tree;
+                } else if roll < density * 0.09 {
+                    *tile = MapTile::Bush;
+                }
+            }
+        }
+
+        let at = |x: f32, y: f32| y as usize * width + x as usize;
+        for dx in -1..=1 {
+            for dy in 0..=1 {
+                tiles[at(tee.0 + dx as f32, tee.1 + dy as f32)] = MapTile::TeeBox;
+            }
+        }
+        tiles[at(tee.0, tee.1)] = MapTile::Tee;
+        let pin = (
+            green.0.round() + rng.range(-1, 2) as f32,
+            green.1.round() + rng.range(-1, 2) as f32,
+        );
+        tiles[at(pin.0, pin.1)] = MapTile::Flag;
+
+        let lines: Vec<String> = tiles
+            .chunks(width)
+            .map(|row| row.iter().map(|tile| tile.to_char()).collect())
+            .collect();
+        Map::from_grid(&lines, None, &Smoothing::default()).expect("Generated a broken hole!")
+    }
+}
+
+fn paint(
+    tiles: &mut [MapTile],
+    spots: &[Spot],
+    center: Spot,
+    radius: f32,
+    tile: MapTile,
+    over: impl Fn(MapTile) -> bool,
+) {
+    for (i, spot) in spots.iter().enumerate() {
+        if distance(*spot, center) <= radius && over(tiles[i]) {
+            tiles[i] = tile;
+        }
+    }
+}
+
+fn distance(a: Spot, b: Spot) -> f32 {
+    ((a.0 - b.0).powf(2.) + (a.1 - b.1).powf(2.)).sqrt()
+}
+
+fn segment_distance(spot: Spot, a: Spot, b: Spot) -> f32 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx * dx + dy * dy;
+    let t = if length > 0. {
+        (((spot.0 - a.0) * dx + (spot.1 - a.1) * dy) / length).clamp(0., 1.)
+    } else {
+        0.
+    };
+    distance(spot, (a.0 + t * dx, a.1 + t * dy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_holes_are_playable_and_longer_for_longer_pars() {
+        let clubs = ClubSet::load(ClubSet::DEFAULT_FILE).unwrap();
+        let mut last = 0.;
+        for (par, &dogleg) in (3..=5).zip([Dogleg::Straight, Dogleg::Left, Dogleg::Right].iter()) {
+            let spec = HoleSpec {
+                dogleg,
+                hazard_density: 1.,
+                ..HoleSpec::new(par as u64, par)
+            };
+            let map = spec.generate(&clubs);
+            assert!(Map::problems(&map.to_lines()).is_empty(), "{:?}", spec);
+            assert!(map.flag_reachable(&clubs), "{:?}", spec);
+            let length = DistanceAlg::Pythagoras.distance2d(map.tee, map.flag);
+            assert!(length > last, "{:?} is {} tiles", spec, length);
+            last = length;
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::prelude::*;
+use serde::Deserialize;
+use std::path::Path;
+
+/** A course file: the holes to play, in order, with their metadata. **/
+#[derive(Debug, Deserialize)]
+pub struct CourseManifest {
+    pub name: String,
+    /// how rough is cut around the fairways on every hole
+    #[serde(default)]
+    pub smoothing: Smoothing,
+    pub holes: Vec<HoleManifest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HoleManifest {
+    pub name: String,
+    pub par: u32,
+    /// stroke index; holes without one are given what is left, longest first
+    pub handicap: Option<u32>,
+    /// measured tee to pin when left out
+    pub yards: Option<u32>,
+    /// path to a map grid from the course file's folder, read with `Map::read_grid`
+    pub map: Option<String>,
+    /// a map grid written out in the course file itself
+    pub grid: Option<String>,
+    /// height grid for an inline `grid`
+    pub elevation: Option<String>,
+    #[serde(default)]
+    pub tees: Vec<Marker>,
+    #[serde(default)]
+    pub pins: Vec<Marker>,
+}
+
+impl HoleManifest {
+    /** The hole's map file, found from `folder`, the course file's own. **/
+    pub fn map_path(&self, folder: &Path) -> Option<String> {
+        self.map
+            .as_ref()
+            .map(|map| folder.join(map).to_string_lossy().into_owned())
+    }
+
+    /** The map and height grid lines, from the map file in `folder` or
+     * written inline; `None` when the hole has neither. **/
+    pub fn grid_lines(&self, folder: &Path) -> Option<Result<GridLines, MapError>> {
+        let to_lines =
+            |text: &String| -> Vec<String> { text.lines().map(|l| l.to_string()).collect() };
+        match (self.map_path(folder), &self.grid) {
+            (Some(path), _) => Some(Map::read_grid(&path)),
+            (None, Some(grid)) => Some(Ok((to_lines(grid), self.elevation.as_ref().map(to_lines)))),
+            (None, None) => None,
+        }
+    }
+}
+
+/** A named tee or pin position, overriding the `T`/`F` in the grid. **/
+#[derive(Debug, Clone, Deserialize)]
+pub struct Marker {
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+}
@@ -1,85 +1,400 @@
 use crate::prelude::*;
+use std::collections::VecDeque;
+use std::fmt;
 use std::fs::File;
 use std::io::*;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum MapError {
+    Io(Error),
+    Empty,
+    DuplicateTee {
+        first: Point,
+        second: Point,
+    },
+    DuplicateFlag {
+        first: Point,
+        second: Point,
+    },
+    MissingTee,
+    MissingFlag,
+    RaggedLine {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    UnknownGlyph {
+        x: usize,
+        y: usize,
+        glyph: char,
+    },
+    TooLarge {
+        width: usize,
+        height: usize,
+    },
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapError::Io(e) => write!(f, "could not read map: {}", e),
+            MapError::Empty => write!(f, "map is empty"),
+            MapError::DuplicateTee { first, second } => write!(
+                f,
+                "second tee at ({}, {}), first at ({}, {})",
+                second.x, second.y, first.x, first.y
+            ),
+            MapError::DuplicateFlag { first, second } => write!(
+                f,
+                "second flag at ({}, {}), first at ({}, {})",
+                second.x, second.y, first.x, first.y
+            ),
+            MapError::MissingTee => write!(f, "no tee (T) on the map"),
+            MapError::MissingFlag => write!(f, "no flag (F) on the map"),
+            MapError::RaggedLine {
+                line,
+                expected,
+                found,
+            } => write!(f, "line {} is {} wide, expected {}", line, found, expected),
+            MapError::UnknownGlyph { x, y, glyph } => {
+                write!(f, "unknown tile '{}' at ({}, {})", glyph, x, y)
+            }
+            MapError::TooLarge { width, height } => write!(
+                f,
+                "map is {}x{}, at most {}x{} is supported",
+                width,
+                height,
+                Map::MAX_SIZE,
+                Map::MAX_SIZE
+            ),
+        }
+    }
+}
+
+impl From<Error> for MapError {
+    fn from(e: Error) -> Self {
+        MapError::Io(e)
+    }
+}
+
+/** The lines of a map grid, and of its height grid if it has one. **/
+pub type GridLines = (Vec<String>, Option<Vec<String>>);
+
+/** What lies under the ball: the tile's surface, its height in meters above
+ * the spot the shot was played from, and its slope as rise per meter (x, y). **/
+#[derive(Debug, Clone, Copy)]
+pub struct Ground {
+    pub surface: Surface,
+    pub height: f32,
+    pub slope: (f32, f32),
+}
 
 #[derive(Debug, Clone)]
 pub struct Map {
-    pub width: u8,
-    pub height: u8,
+    pub width: u16,
+    pub height: u16,
     points: Vec<MapTile>,
+    // the grid as written, before its rough was cut; what the editor saves
+    source: Vec<MapTile>,
+    elevation: Vec<f32>,
+    // lowest and highest ground on the map
+    relief: (f32, f32),
     pub tee: Point,
     pub flag: Point,
 }
 
 impl Map {
-    pub fn load_map(filename: &str) -> Option<Self> {
-        let p = std::env::current_dir();
-        println!("Path: {:?}", p);
-        let f: File = File::open(filename).unwrap();
+    pub const METERS_PER_TILE: f32 = 8.33333;
+    pub const MAX_SIZE: usize = u16::MAX as usize;
+    // two club lengths from where a ball went into a hazard, rounded out
+    // to take in the tiles around it
+    const DROP_REACH: f32 = 1.5;
+    // each digit in a height grid raises the tile this many meters
+    const METERS_PER_LEVEL: f32 = 0.5;
+
+    /** Digits in a height grid raise their tile; a map without one is flat. **/
+    fn elevation(heights: Option<&[String]>, width: u16, height: u16) -> Vec<f32> {
+        let mut elevation = vec![0.; height as usize * width as usize];
+        for (y, line) in heights
+            .unwrap_or_default()
+            .iter()
+            .take(height as usize)
+            .enumerate()
+        {
+            for (x, c) in line.chars().take(width as usize).enumerate() {
+                let level = c.to_digit(10).unwrap_or(0) as f32;
+                elevation[(y * width as usize) + x] = level * Map::METERS_PER_LEVEL;
+            }
+        }
+        elevation
+    }
+
+    /** The raw lines of a map file and of the optional height grid next to
+     * it (`map1.txt` -> `map1.height.txt`). **/
+    pub fn read_grid(filename: &str) -> std::result::Result<GridLines, MapError> {
+        let f: File = File::open(filename)?;
         let l: BufReader<File> = BufReader::new(f);
-        let mut tee = None;
-        let mut flag = None;
-        let lines: Vec<String> = l.lines().map(|l| l.unwrap()).collect::<Vec<String>>();
-        let width: u8 = lines.iter().take(1).next().unwrap().chars().count() as u8;
-        let height: u8 = lines.len() as u8;
-        let mut buf = vec![MapTile::Rough; height as usize * width as usize];
+        let lines: Vec<String> = l.lines().collect::<std::result::Result<Vec<String>, _>>()?;
+        let heights: Option<Vec<String>> =
+            File::open(Path::new(filename).with_extension("height.txt"))
+                .ok()
+                .map(|f| BufReader::new(f).lines().map_while(Result::ok).collect());
+        Ok((lines, heights))
+    }
+
+    /** Parse a grid and smooth its rough, as the game plays it. **/
+    pub fn from_grid(
+        lines: &[String],
+        heights: Option<&[String]>,
+        smoothing: &Smoothing,
+    ) -> std::result::Result<Self, MapError> {
+        let mut map = Map::parse(lines, heights)?;
+        map.smooth_rough(smoothing);
+        Ok(map)
+    }
+
+    // a trailing blank line is not a row of the map
+    fn rows(lines: &[String]) -> &[String] {
+        let rows = lines
+            .iter()
+            .rposition(|l| !l.is_empty())
+            .map_or(0, |last| last + 1);
+        &lines[..rows]
+    }
+
+    /** Everything wrong with a grid, in reading order. **/
+    pub fn problems(lines: &[String]) -> Vec<MapError> {
+        let lines = Map::rows(lines);
+        let columns = match lines.first() {
+            Some(line) => line.chars().count(),
+            None => return vec![MapError::Empty],
+        };
+        let mut problems = Vec::new();
+        if columns > Map::MAX_SIZE || lines.len() > Map::MAX_SIZE {
+            problems.push(MapError::TooLarge {
+                width: columns,
+                height: lines.len(),
+            });
+        }
+        let mut tee: Option<Point> = None;
+        let mut flag: Option<Point> = None;
         for (y, line) in lines.iter().enumerate() {
-            for (x, c) in line.chars().take(width as usize).enumerate() {
-                let tile = MapTile::from_char(&c);
-                match tile {
-                    MapTile::Tee => {
-                        if tee.is_some() {
-                            panic!("Too many tees!")
-                        } else {
-                            tee = Some(Point::new(x, y));
-                        }
-                    }
-                    MapTile::Flag => {
-                        if flag.is_some() {
-                            panic!("Too many flags!")
-                        } else {
-                            flag = Some(Point::new(x, y));
-                        }
-                    }
-                    _ => {}
+            let found = line.chars().count();
+            if found != columns {
+                problems.push(MapError::RaggedLine {
+                    line: y + 1,
+                    expected: columns,
+                    found,
+                });
+            }
+            for (x, c) in line.chars().enumerate() {
+                match MapTile::from_char(&c) {
+                    None => problems.push(MapError::UnknownGlyph { x, y, glyph: c }),
+                    Some(MapTile::Tee) => match tee {
+                        Some(first) => problems.push(MapError::DuplicateTee {
+                            first,
+                            second: Point::new(x, y),
+                        }),
+                        None => tee = Some(Point::new(x, y)),
+                    },
+                    Some(MapTile::Flag) => match flag {
+                        Some(first) => problems.push(MapError::DuplicateFlag {
+                            first,
+                            second: Point::new(x, y),
+                        }),
+                        None => flag = Some(Point::new(x, y)),
+                    },
+                    Some(_) => {}
                 }
-                let n = (y * width as usize) + x;
-                buf[n] = tile;
             }
         }
-        let mut map = Self {
+        if tee.is_none() {
+            problems.push(MapError::MissingTee);
+        }
+        if flag.is_none() {
+            problems.push(MapError::MissingFlag);
+        }
+        problems
+    }
+
+    /** Read a grid exactly as written, failing on its first problem. **/
+    pub fn parse(
+        lines: &[String],
+        heights: Option<&[String]>,
+    ) -> std::result::Result<Self, MapError> {
+        if let Some(problem) = Map::problems(lines).into_iter().next() {
+            return Err(problem);
+        }
+        // from here the grid is rectangular, known tiles only, one tee and one flag
+        let lines = Map::rows(lines);
+        let columns = lines[0].chars().count();
+        let width = columns as u16;
+        let height = lines.len() as u16;
+        let points: Vec<MapTile> = lines
+            .iter()
+            .flat_map(|line| line.chars())
+            .filter_map(|c| MapTile::from_char(&c))
+            .collect();
+        let find = |wanted: MapTile| {
+            let n = points.iter().position(|tile| *tile == wanted).unwrap_or(0);
+            Point::new(n % columns, n / columns)
+        };
+        let tee = find(MapTile::Tee);
+        let flag = find(MapTile::Flag);
+        let elevation = Map::elevation(heights, width, height);
+        let relief = (
+            elevation.iter().cloned().fold(f32::MAX, f32::min),
+            elevation.iter().cloned().fold(f32::MIN, f32::max),
+        );
+        Ok(Self {
             width,
             height,
-            points: buf.clone(),
-            tee: tee?,
-            flag: flag?,
-        };
-        let mut fairway_adjacency = vec![0 as usize; height as usize * width as usize];
-        for (i, point) in map.points.iter().enumerate() {
+            source: points.clone(),
+            points,
+            elevation,
+            relief,
+            tee,
+            flag,
+        })
+    }
+
+    /** Cut deep rough back around fairway (or whatever `smoothing` feeds
+     * it from): first cut right beside, rough where there is enough nearby. **/
+    pub fn smooth_rough(&mut self, smoothing: &Smoothing) {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let reach = smoothing.radius.max(smoothing.first_cut);
+        let mut cut = self.points.clone();
+        for (i, point) in self.points.iter().enumerate() {
             if let MapTile::DeepRough = point {
-                let y = i as i32 / map.width as i32;
-                let x = i as i32 % map.width as i32;
-                let deep_rad = 8;
-                let min_x = (x - deep_rad).max(0);
-                let min_y = (y - deep_rad).max(0);
-                let max_x = (x + deep_rad).min(width as i32 - 1);
-                let max_y = (y + deep_rad).min(height as i32 - 1);
-                for ix in min_x..max_x {
-                    for iy in min_y..max_y {
-                        if let MapTile::Fairway = &map.tile_at_xy(ix as u8, iy as u8) {
-                            fairway_adjacency[i] += 1;
+                let y = i as i32 / width;
+                let x = i as i32 % width;
+                let mut feeders = 0;
+                let mut nearest = i32::MAX;
+                for iy in (y - reach).max(0)..=(y + reach).min(height - 1) {
+                    for ix in (x - reach).max(0)..=(x + reach).min(width - 1) {
+                        let tile = self.tile_at_xy(ix as u16, iy as u16);
+                        if !smoothing.feeders.contains(&tile.to_char()) {
+                            continue;
+                        }
+                        let (dx, dy) = (ix - x, iy - y);
+                        nearest = nearest.min(dx * dx + dy * dy);
+                        if dx.abs() <= smoothing.radius && dy.abs() <= smoothing.radius {
+                            feeders += 1;
                         }
                     }
                 }
+                if nearest <= smoothing.first_cut * smoothing.first_cut {
+                    cut[i] = MapTile::FirstCut;
+                } else if feeders > smoothing.threshold {
+                    cut[i] = MapTile::Rough;
+                }
             }
         }
-        for (i, near_fairway) in fairway_adjacency.iter().enumerate() {
-            if *near_fairway > 3 {
-                buf[i] = MapTile::Rough;
+        self.points = cut;
+    }
+
+    /** Lay `tile` at `point`. A new tee or flag takes over from the old one,
+     * which is left as tee box or green. Neither the grid's `T` and `F` nor
+     * the tee and flag in play can be painted over. **/
+    pub fn paint(&mut self, point: &Point, tile: MapTile) {
+        if !self.in_bounds(point) {
+            return;
+        }
+        let n = ((point.y * self.width as i32) + point.x) as usize;
+        let marked = |at: Point, written: MapTile| *point == at || self.source[n] == written;
+        let moving = (marked(self.tee, MapTile::Tee) && tile != MapTile::Tee)
+            || (marked(self.flag, MapTile::Flag) && tile != MapTile::Flag);
+        if moving {
+            return;
+        }
+        let replace = |points: &mut Vec<MapTile>, old: MapTile, left: MapTile| {
+            points
+                .iter_mut()
+                .filter(|t| **t == old)
+                .for_each(|t| *t = left)
+        };
+        match tile {
+            MapTile::Tee => {
+                replace(&mut self.points, MapTile::Tee, MapTile::TeeBox);
+                replace(&mut self.source, MapTile::Tee, MapTile::TeeBox);
+                self.tee = *point;
+            }
+            MapTile::Flag => {
+                replace(&mut self.points, MapTile::Flag, MapTile::Green);
+                replace(&mut self.source, MapTile::Flag, MapTile::Green);
+                self.flag = *point;
             }
+            _ => {}
+        }
+        self.points[n] = tile;
+        self.source[n] = tile;
+    }
+
+    /** Write the grid out as it was written, with its edits, so loading it
+     * again cuts the rough the same way. **/
+    pub fn save(&self, filename: &str) -> Result<()> {
+        let mut f = File::create(filename)?;
+        for line in Map::lines_of(&self.source, self.width) {
+            writeln!(f, "{}", line)?;
         }
-        map.points = buf;
-        Some(map)
+        Ok(())
+    }
+
+    /** The grid written back out in the characters it is read from. **/
+    pub fn to_lines(&self) -> Vec<String> {
+        Map::lines_of(&self.points, self.width)
+    }
+
+    fn lines_of(points: &[MapTile], width: u16) -> Vec<String> {
+        points
+            .chunks(width as usize)
+            .map(|row| row.iter().map(|tile| tile.to_char()).collect())
+            .collect()
+    }
+
+    /** Whether some chain of shots, none longer than the bag allows from its lie
+     * and each coming to rest on playable ground, gets from the tee to the flag. **/
+    pub fn flag_reachable(&self, clubs: &ClubSet) -> bool {
+        // longest shot in tiles, worked out once per kind of lie
+        let mut reach: Vec<(MapTile, f32)> = Vec::new();
+        let mut visited = vec![false; self.width as usize * self.height as usize];
+        let index = |p: &Point| (p.y as usize * self.width as usize) + p.x as usize;
+        let mut queue = VecDeque::new();
+        visited[index(&self.tee)] = true;
+        queue.push_back(self.tee);
+        while let Some(from) = queue.pop_front() {
+            if from == self.flag {
+                return true;
+            }
+            let lie = self.tile_at(&from);
+            let radius = match reach.iter().find(|(tile, _)| *tile == lie) {
+                Some((_, radius)) => *radius,
+                None => {
+                    let radius = clubs
+                        .iter()
+                        .map(|club| Travel::full_distance(club, &lie.surface()))
+                        .fold(0., f32::max);
+                    reach.push((lie, radius));
+                    radius
+                }
+            };
+            let r = radius.floor() as i32;
+            for dy in -r..=r {
+                for dx in -r..=r {
+                    let to = from + Point::new(dx, dy);
+                    if (dx * dx + dy * dy) as f32 > radius * radius || !self.in_bounds(&to) {
+                        continue;
+                    }
+                    if visited[index(&to)] || self.tile_at(&to).is_hazard() {
+                        continue;
+                    }
+                    visited[index(&to)] = true;
+                    queue.push_back(to);
+                }
+            }
+        }
+        false
     }
 
     pub fn intersection(&self, p1: Point, p2: Point) -> Point {
@@ -107,7 +422,6 @@ impl Map {
                 let m = dy as f32 / dx as f32;
                 let xdiff = x - p1.x;
                 let y = (p1.y as f32 + (xdiff as f32 * m)) as i32;
-                println!("Known x {:?}, calc y {:?}", x, y);
                 Point::new(x, y)
             }
             (None, Some(y)) => {
@@ -119,7 +433,6 @@ impl Map {
                     let m = dy as f32 / dx as f32;
                     let ydiff = y - p1.y;
                     let x = (p1.x as f32 + (ydiff as f32 / m)) as i32;
-                    println!("Known y {:?}, calc x {:?}", y, x);
                     Point::new(x, y)
                 }
             }
@@ -132,16 +445,329 @@ impl Map {
     }
 
     pub fn tile_at(&self, point: &Point) -> MapTile {
-        let n = ((point.y * self.width as i32) + point.x as i32) as usize;
+        let n = ((point.y * self.width as i32) + point.x) as usize;
         self.points[n]
     }
 
-    pub fn tile_at_xy(&self, x: u8, y: u8) -> MapTile {
-        let n = ((y as u16 * self.width as u16) + x as u16) as usize;
+    /** The tile as it is drawn: the tee and flag where they are played, and
+     * the grid's own `T` and `F` as the tee box and green under them. **/
+    pub fn shown_at(&self, point: &Point) -> MapTile {
+        if *point == self.tee {
+            return MapTile::Tee;
+        }
+        if *point == self.flag {
+            return MapTile::Flag;
+        }
+        match self.tile_at(point) {
+            MapTile::Tee => MapTile::TeeBox,
+            MapTile::Flag => MapTile::Green,
+            tile => tile,
+        }
+    }
+
+    pub fn tile_at_xy(&self, x: u16, y: u16) -> MapTile {
+        let n = (y as usize * self.width as usize) + x as usize;
         self.points[n]
     }
 
+    /** The tiles on the map a ball crosses moving from `from` to `to`, after
+     * the one it started on, with the ground of each seen from `origin`. **/
+    pub fn crossed(&self, from: &Point, to: &Point, origin: &Point) -> Vec<(MapTile, Ground)> {
+        line2d_bresenham(*from, *to)
+            .into_iter()
+            .skip(1)
+            .filter(|p| self.in_bounds(p))
+            .map(|p| (self.tile_at(&p), self.ground_at(&p, origin)))
+            .collect()
+    }
+
+    /** Whether any green lies within `reach` tiles of `point`. **/
+    pub fn near_green(&self, point: &Point, reach: i32) -> bool {
+        (-reach..=reach)
+            .cartesian_product(-reach..=reach)
+            .map(|(dx, dy)| Point::new(point.x + dx, point.y + dy))
+            .any(|p| self.in_bounds(&p) && self.tile_at(&p).surface().stimp.is_some())
+    }
+
+    /** Where to drop after finding a hazard at `at`: the nearest dry tile
+     * beside where the ball went in on its way from `from`, no nearer the hole. **/
+    pub fn drop_point(&self, from: &Point, at: &Point) -> Point {
+        let line = line2d_bresenham(*at, *from);
+        // the hazard tile the ball crossed into, and the dry one before it
+        let (entry, back) = match line
+            .iter()
+            .position(|p| self.in_bounds(p) && !self.tile_at(p).is_hazard())
+        {
+            Some(0) => return *at,
+            Some(dry) => (line[dry - 1], line[dry]),
+            None => return *from,
+        };
+        let to_hole = |p: &Point| DistanceAlg::Pythagoras.distance2d(*p, self.flag);
+        let to_entry = |p: &Point| DistanceAlg::Pythagoras.distance2d(*p, entry);
+        let reach = Map::DROP_REACH.ceil() as i32;
+        (-reach..=reach)
+            .cartesian_product(-reach..=reach)
+            .map(|(dx, dy)| Point::new(entry.x + dx, entry.y + dy))
+            .filter(|p| self.in_bounds(p) && !self.tile_at(p).is_hazard())
+            .filter(|p| to_entry(p) <= Map::DROP_REACH && to_hole(p) >= to_hole(&entry))
+            .min_by(|a, b| {
+                to_entry(a)
+                    .partial_cmp(&to_entry(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap_or(back)
+    }
+
+    pub fn elevation_at(&self, point: &Point) -> f32 {
+        if self.in_bounds(point) {
+            let n = ((point.y * self.width as i32) + point.x) as usize;
+            self.elevation[n]
+        } else {
+            0.
+        }
+    }
+
+    /** Rise per meter across `point`, from its neighbours on either side. **/
+    pub fn slope_at(&self, point: &Point) -> (f32, f32) {
+        let clamp = |p: Point| {
+            Point::new(
+                p.x.max(0).min(self.width as i32 - 1),
+                p.y.max(0).min(self.height as i32 - 1),
+            )
+        };
+        let gradient = |a: Point, b: Point| {
+            let (a, b) = (clamp(a), clamp(b));
+            let run = ((b.x - a.x) + (b.y - a.y)) as f32 * Map::METERS_PER_TILE;
+            if run > 0. {
+                (self.elevation_at(&b) - self.elevation_at(&a)) / run
+            } else {
+                0.
+            }
+        };
+        (
+            gradient(*point - Point::new(1, 0), *point + Point::new(1, 0)),
+            gradient(*point - Point::new(0, 1), *point + Point::new(0, 1)),
+        )
+    }
+
+    pub fn ground_at(&self, point: &Point, origin: &Point) -> Ground {
+        Ground {
+            surface: self.tile_at(point).surface(),
+            height: self.elevation_at(point) - self.elevation_at(origin),
+            slope: self.slope_at(point),
+        }
+    }
+
+    /** Where `point` sits between the lowest (-1) and highest (1) ground on the map. **/
+    pub fn shade(&self, point: &Point) -> f32 {
+        let (low, high) = self.relief;
+        if high > low {
+            (self.elevation_at(point) - low) / (high - low) * 2. - 1.
+        } else {
+            0.
+        }
+    }
+
     pub fn bg(&self, position: &Point) -> (u8, u8, u8) {
         self.tile_at(position).bg()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    #[test]
+    fn a_clean_grid_has_no_problems() {
+        assert!(Map::problems(&grid(&["..F..", ".....", "..T..", ""])).is_empty());
+    }
+
+    #[test]
+    fn problems_are_found_in_reading_order() {
+        let problems = Map::problems(&grid(&["T.F", "T..", "..?", ".."]));
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert!(matches!(
+            problems[0],
+            MapError::DuplicateTee { second, .. } if second == Point::new(0, 1)
+        ));
+        assert!(matches!(
+            problems[1],
+            MapError::UnknownGlyph {
+                x: 2,
+                y: 2,
+                glyph: '?'
+            }
+        ));
+        assert!(matches!(
+            problems[2],
+            MapError::RaggedLine {
+                line: 4,
+                expected: 3,
+                found: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn a_grid_needs_a_tee_and_a_flag() {
+        assert!(matches!(Map::problems(&[]).as_slice(), [MapError::Empty]));
+        assert!(matches!(
+            Map::problems(&grid(&["...", "..."])).as_slice(),
+            [MapError::MissingTee, MapError::MissingFlag]
+        ));
+        assert!(matches!(
+            Map::parse(&grid(&["F.F", ".T."]), None),
+            Err(MapError::DuplicateFlag { .. })
+        ));
+    }
+
+    #[test]
+    fn a_grid_taller_than_255_rows_keeps_its_height() {
+        let mut rows = vec!["...".to_string(); 300];
+        rows[0] = ".F.".to_string();
+        rows[299] = ".T.".to_string();
+        let map = Map::parse(&rows, None).unwrap();
+        assert_eq!(map.height, 300);
+        assert_eq!(map.tee, Point::new(1, 299));
+    }
+
+    #[test]
+    fn a_grid_past_the_largest_size_is_too_large() {
+        let mut rows = vec![".".to_string(); Map::MAX_SIZE + 1];
+        rows[0] = "F".to_string();
+        rows[1] = "T".to_string();
+        assert!(matches!(
+            Map::parse(&rows, None),
+            Err(MapError::TooLarge { width: 1, height }) if height == Map::MAX_SIZE + 1
+        ));
+    }
+
+    #[test]
+    fn rough_is_cut_around_the_fairway() {
+        let mut map = Map::parse(
+            &grid(&[
+                "T.........",
+                "....====..",
+                "..........",
+                "..........",
+                ".........F",
+            ]),
+            None,
+        )
+        .unwrap();
+        map.smooth_rough(&Smoothing {
+            feeders: vec!['='],
+            radius: 2,
+            threshold: 2,
+            first_cut: 1,
+        });
+        assert_eq!(map.tile_at(&Point::new(5, 0)), MapTile::FirstCut);
+        assert_eq!(map.tile_at(&Point::new(5, 2)), MapTile::FirstCut);
+        assert_eq!(map.tile_at(&Point::new(5, 3)), MapTile::Rough);
+        assert_eq!(map.tile_at(&Point::new(8, 1)), MapTile::FirstCut);
+        // out of range, or with too few feeders in range
+        assert_eq!(map.tile_at(&Point::new(0, 4)), MapTile::DeepRough);
+        assert_eq!(map.tile_at(&Point::new(9, 1)), MapTile::DeepRough);
+        // only deep rough is cut
+        assert_eq!(map.tile_at(&Point::new(4, 1)), MapTile::Fairway);
+    }
+
+    #[test]
+    fn a_flag_past_a_lake_too_wide_to_carry_is_unreachable() {
+        let clubs = ClubSet::load(ClubSet::DEFAULT_FILE).unwrap();
+        let carry = clubs
+            .iter()
+            .map(|club| Travel::full_distance(club, &Surface::TEE_BOX))
+            .fold(0., f32::max)
+            .ceil() as usize;
+        let lake = |width: usize| {
+            let mut rows = vec!["..F..".to_string()];
+            rows.extend(vec!["~~~~~".to_string(); width]);
+            rows.push("..T..".to_string());
+            Map::parse(&rows, None).unwrap()
+        };
+        assert!(lake(carry - 2).flag_reachable(&clubs));
+        assert!(!lake(carry + 2).flag_reachable(&clubs));
+    }
+
+    #[test]
+    fn a_saved_map_keeps_its_rough_uncut() {
+        let rows = grid(&["T.....", ".====.", "......", ".....F"]);
+        let mut map = Map::from_grid(&rows, None, &Smoothing::default()).unwrap();
+        assert_eq!(map.tile_at(&Point::new(0, 2)), MapTile::Rough);
+        map.paint(&Point::new(0, 2), MapTile::Bunker);
+        let path = std::env::temp_dir().join("saved_map.txt");
+        map.save(path.to_str().unwrap()).unwrap();
+        let (saved, _) = Map::read_grid(path.to_str().unwrap()).unwrap();
+        assert_eq!(saved, grid(&["T.....", ".====.", "S.....", ".....F"]));
+    }
+
+    #[test]
+    fn the_grids_tee_and_flag_cannot_be_painted_over() {
+        let mut map = Map::parse(&grid(&["..F..", ".....", "..T.."]), None).unwrap();
+        // a named tee and pin in play elsewhere
+        map.tee = Point::new(0, 2);
+        map.flag = Point::new(4, 0);
+        map.paint(&Point::new(2, 2), MapTile::Water);
+        map.paint(&Point::new(2, 0), MapTile::Water);
+        map.paint(&Point::new(0, 2), MapTile::Water);
+        assert_eq!(map.to_lines(), grid(&["..F..", ".....", "..T.."]));
+    }
+
+    #[test]
+    fn every_tile_crossed_in_a_frame_is_checked() {
+        let map = Map::parse(&grid(&["T.Y.F"]), None).unwrap();
+        let crossed = map.crossed(&map.tee, &map.flag, &map.tee);
+        let tiles: Vec<MapTile> = crossed.iter().map(|(tile, _)| *tile).collect();
+        assert_eq!(tiles.len(), 4);
+        assert!(tiles.contains(&MapTile::Tree));
+        assert!(!tiles.contains(&MapTile::Tee));
+    }
+
+    #[test]
+    fn a_drop_is_dry_beside_the_water_and_no_nearer_the_hole() {
+        let map = Map::parse(
+            &grid(&[
+                "....F.....",
+                "..........",
+                "..........",
+                ".~~~~.....",
+                ".~~~~.....",
+                "..........",
+                "..T.......",
+            ]),
+            None,
+        )
+        .unwrap();
+        let entry = Point::new(2, 4);
+        let drop = map.drop_point(&map.tee, &Point::new(2, 3));
+        assert!(!map.tile_at(&drop).is_hazard());
+        assert!(DistanceAlg::Pythagoras.distance2d(drop, entry) <= Map::DROP_REACH);
+        let to_hole = |p: Point| DistanceAlg::Pythagoras.distance2d(p, map.flag);
+        assert!(to_hole(drop) >= to_hole(entry));
+    }
+
+    #[test]
+    fn a_drop_goes_to_the_side_rather_than_nearer_the_hole() {
+        // played away from the flag into a creek; back on the line is nearer the hole
+        let map = Map::parse(
+            &grid(&[
+                "..F..", //
+                ".....", //
+                "..T..", //
+                ".....", //
+                "..~..", //
+                "..~..", //
+            ]),
+            None,
+        )
+        .unwrap();
+        let drop = map.drop_point(&map.tee, &Point::new(2, 5));
+        assert!(!map.tile_at(&drop).is_hazard());
+        assert_eq!(drop.y, 4, "dropped at {:?}", drop);
+    }
+}
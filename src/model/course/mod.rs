@@ -1,32 +1,654 @@
+pub mod generator;
+pub mod manifest;
 pub mod map;
+pub mod terrain;
+pub use generator::*;
+pub use manifest::*;
 pub use map::*;
+pub use terrain::*;
 
-use std::collections::LinkedList;
+use crate::prelude::*;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+#[derive(Debug)]
+pub enum CourseError {
+    Io(io::Error),
+    Manifest(toml::de::Error),
+    NoHoles,
+    MissingGrid { hole: usize },
+    Map { hole: usize, error: MapError },
+    OffMap { hole: usize, marker: Marker },
+    Clubs(ClubError),
+}
+
+impl fmt::Display for CourseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CourseError::Io(e) => write!(f, "could not read course: {}", e),
+            CourseError::Manifest(e) => write!(f, "bad course file: {}", e),
+            CourseError::NoHoles => write!(f, "course has no holes"),
+            CourseError::MissingGrid { hole } => {
+                write!(f, "hole {} needs a map or a grid", hole)
+            }
+            CourseError::Map { hole, error } => write!(f, "hole {}: {}", hole, error),
+            CourseError::OffMap { hole, marker } => write!(
+                f,
+                "hole {}: {} at ({}, {}) is off the map",
+                hole, marker.name, marker.x, marker.y
+            ),
+            CourseError::Clubs(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/** Whether the course loaded, for the menu to show. **/
+pub enum CourseStatus {
+    Ready(String),
+    Failed(CourseError),
+}
+
+/** Which tees the round is played from, which day's pin positions, how
+ * many golfers play it and how many of those the computer plays for, their
+ * handicaps and how it is scored. **/
+pub struct Setup {
+    tee_names: Vec<String>,
+    tee: usize,
+    pub day: usize,
+    today: usize,
+    pub players: usize,
+    pub computers: usize,
+    pub handicaps: Vec<u32>,
+    pub format: Format,
+}
+
+impl Setup {
+    const SECONDS_PER_DAY: u64 = 86_400;
+    const MAX_HANDICAP: u32 = 36;
+
+    /** The first tees, and pins rotated by today's date. **/
+    pub fn new(tee_names: Vec<String>) -> Self {
+        let day = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() / Setup::SECONDS_PER_DAY);
+        Setup {
+            tee_names,
+            tee: 0,
+            day: day as usize,
+            today: day as usize,
+            players: 1,
+            computers: 0,
+            handicaps: vec![0; Player::MAX_PLAYERS],
+            format: Format::StrokePlay,
+        }
+    }
+
+    pub fn tees(&self) -> Option<&str> {
+        self.tee_names.get(self.tee).map(|name| name.as_str())
+    }
+
+    pub fn next_tees(&mut self) {
+        if !self.tee_names.is_empty() {
+            self.tee = (self.tee + 1) % self.tee_names.len();
+        }
+    }
+
+    pub fn previous_tees(&mut self) {
+        if !self.tee_names.is_empty() {
+            self.tee = (self.tee + self.tee_names.len() - 1) % self.tee_names.len();
+        }
+    }
+
+    pub fn next_players(&mut self) {
+        self.players = self.players % Player::MAX_PLAYERS + 1;
+        self.computers = self.computers.min(self.players);
+    }
+
+    /** Hand one more of the golfers, from the last, to the computer. **/
+    pub fn next_computers(&mut self) {
+        self.computers = (self.computers + 1) % (self.players + 1);
+    }
+
+    /** One more stroke for `player`, back to scratch past the limit. **/
+    pub fn raise_handicap(&mut self, player: usize) {
+        if let Some(handicap) = self.handicaps.get_mut(player) {
+            *handicap = (*handicap + 1) % (Setup::MAX_HANDICAP + 1);
+        }
+    }
+
+    /** The rules for a round over the holes with stroke `indexes`. **/
+    pub fn scoring(&self, indexes: Option<Vec<u32>>) -> Scoring {
+        self.format
+            .scoring(self.players, indexes.as_deref(), &self.handicaps)
+    }
+
+    pub fn next_format(&mut self) {
+        self.format = self.format.next();
+    }
+
+    /** Play a later day's pins. **/
+    pub fn next_pins(&mut self) {
+        self.day += 1;
+    }
+
+    pub fn previous_pins(&mut self) {
+        if self.day > self.today {
+            self.day -= 1;
+        }
+    }
+
+    pub fn pin_sheet(&self) -> String {
+        match self.day - self.today {
+            0 => "today".to_string(),
+            1 => "tomorrow".to_string(),
+            days => format!("in {} days", days),
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Hole {
     pub number: usize,
+    pub name: String,
+    pub par: u32,
+    pub handicap: u32,
+    pub yards: u32,
+    pub tee_name: Option<String>,
+    /// the map file the hole was read from, if it has one
+    pub source: Option<String>,
     pub map: Map,
+    // named positions overriding the grid's `T` and `F`
+    tees: Vec<Marker>,
+    pins: Vec<Marker>,
+    // yardage given in the course file, kept whichever tees are played
+    listed_yards: Option<u32>,
+}
+
+impl Hole {
+    const YARDS_PER_METER: f32 = 1.09361;
+
+    fn from_manifest(
+        number: usize,
+        manifest: HoleManifest,
+        folder: &Path,
+        smoothing: &Smoothing,
+    ) -> Result<Self, CourseError> {
+        let map = manifest
+            .grid_lines(folder)
+            .ok_or(CourseError::MissingGrid { hole: number })?
+            .and_then(|(lines, heights)| Map::from_grid(&lines, heights.as_deref(), smoothing))
+            .map_err(|error| CourseError::Map {
+                hole: number,
+                error,
+            })?;
+        if let Some(marker) = manifest
+            .tees
+            .iter()
+            .chain(manifest.pins.iter())
+            .find(|m| !map.in_bounds(&Point::new(m.x, m.y)))
+        {
+            return Err(CourseError::OffMap {
+                hole: number,
+                marker: marker.clone(),
+            });
+        }
+        let source = manifest.map_path(folder);
+        let mut hole = Hole {
+            number,
+            name: manifest.name,
+            par: manifest.par,
+            handicap: manifest.handicap.unwrap_or(0),
+            yards: 0,
+            tee_name: None,
+            source,
+            map,
+            tees: manifest.tees,
+            pins: manifest.pins,
+            listed_yards: manifest.yards,
+        };
+        hole.place(&Setup::new(Vec::new()));
+        Ok(hole)
+    }
+
+    fn generated(number: usize, spec: &HoleSpec, clubs: &ClubSet) -> Self {
+        let map = spec.generate(clubs);
+        Hole {
+            number,
+            name: format!("Seed {}", spec.seed),
+            par: spec.par,
+            // practice runs through the indexes of an eighteen in turn
+            handicap: (number as u32 - 1) % 18 + 1,
+            yards: Hole::measure(&map),
+            tee_name: None,
+            source: None,
+            map,
+            tees: Vec::new(),
+            pins: Vec::new(),
+            listed_yards: None,
+        }
+    }
+
+    /** Move the tee and flag to the setup's tees and the day's pin. A hole
+     * without those tees plays from its first; one without named tees or
+     * pins plays from its grid. **/
+    pub fn place(&mut self, setup: &Setup) {
+        let tee = self
+            .tees
+            .iter()
+            .find(|t| Some(t.name.as_str()) == setup.tees())
+            .or(self.tees.first())
+            .cloned();
+        if let Some(tee) = tee {
+            self.map.tee = Point::new(tee.x, tee.y);
+            self.tee_name = Some(tee.name.clone());
+        }
+        if !self.pins.is_empty() {
+            let pin = &self.pins[setup.day % self.pins.len()];
+            self.map.flag = Point::new(pin.x, pin.y);
+        }
+        self.yards = self
+            .listed_yards
+            .unwrap_or_else(|| Hole::measure(&self.map));
+    }
+
+    /** Move the tee in play to `point`: the named tee being played, or the
+     * grid's `T` on a hole without named tees. Gives the name of the marker
+     * moved, if one was. **/
+    pub fn move_tee(&mut self, point: &Point) -> Option<String> {
+        let name = self.tee_name.clone();
+        match self
+            .tees
+            .iter_mut()
+            .find(|t| Some(&t.name) == name.as_ref())
+        {
+            Some(tee) if self.map.in_bounds(point) && *point != self.map.flag => {
+                tee.x = point.x;
+                tee.y = point.y;
+                self.map.tee = *point;
+                name
+            }
+            Some(_) => None,
+            None => {
+                self.map.paint(point, MapTile::Tee);
+                None
+            }
+        }
+    }
+
+    /** Move the flag in play to `point`: the day's pin, or the grid's `F` on
+     * a hole without pins. Gives the name of the pin moved, if one was. **/
+    pub fn move_flag(&mut self, point: &Point) -> Option<String> {
+        let flag = self.map.flag;
+        match self.pins.iter_mut().find(|p| Point::new(p.x, p.y) == flag) {
+            Some(pin) if self.map.in_bounds(point) && *point != self.map.tee => {
+                pin.x = point.x;
+                pin.y = point.y;
+                self.map.flag = *point;
+                Some(pin.name.clone())
+            }
+            Some(_) => None,
+            None => {
+                self.map.paint(point, MapTile::Flag);
+                None
+            }
+        }
+    }
+
+    pub fn tee_names(&self) -> impl Iterator<Item = &String> {
+        self.tees.iter().map(|t| &t.name)
+    }
+
+    /** Yards from tee to pin as the crow flies. **/
+    fn measure(map: &Map) -> u32 {
+        let tiles = DistanceAlg::Pythagoras.distance2d(map.tee, map.flag);
+        (tiles * Map::METERS_PER_TILE * Hole::YARDS_PER_METER).round() as u32
+    }
+}
+
+/** Which of the course's holes make up the round. **/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Round {
+    Front,
+    Back,
+    Full,
+}
+
+impl Round {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Round::Front => "Front nine",
+            Round::Back => "Back nine",
+            Round::Full => "Full round",
+        }
+    }
+
+    fn next(&self) -> Round {
+        match self {
+            Round::Full => Round::Front,
+            Round::Front => Round::Back,
+            Round::Back => Round::Full,
+        }
+    }
 }
 
 pub struct Course {
-    holes: LinkedList<Hole>,
+    pub name: String,
+    holes: Vec<Hole>,
+    round: Round,
+    // index of the hole being played
+    cursor: usize,
+    // generates holes past the last one listed
+    practice: Option<Practice>,
 }
 
 impl Course {
-    pub fn default() -> Self {
-        let hole_files = vec!["src/map1.txt", "src/map2.txt"];
-        let mut holes: LinkedList<Hole> = LinkedList::new();
-        holes.extend(
-            hole_files
-                .iter()
-                .map(|p| Map::load_map(p).unwrap())
-                .enumerate()
-                .map(|(i, map)| Hole { number: i + 1, map }),
+    pub const DEFAULT_FILE: &'static str = "src/course.toml";
+    const NINE: usize = 9;
+
+    pub fn load(filename: &str) -> Result<Self, CourseError> {
+        let text = fs::read_to_string(filename).map_err(CourseError::Io)?;
+        let manifest: CourseManifest = toml::from_str(&text).map_err(CourseError::Manifest)?;
+        if manifest.holes.is_empty() {
+            return Err(CourseError::NoHoles);
+        }
+        // map files are named from the course file's folder
+        let folder = Path::new(filename)
+            .parent()
+            .unwrap_or_else(|| Path::new(""));
+        let mut holes: Vec<Hole> = Vec::new();
+        for (i, hole) in manifest.holes.into_iter().enumerate() {
+            holes.push(Hole::from_manifest(
+                i + 1,
+                hole,
+                folder,
+                &manifest.smoothing,
+            )?);
+        }
+        Course::index_holes(&mut holes);
+        Ok(Self {
+            name: manifest.name,
+            holes,
+            round: Round::Full,
+            cursor: 0,
+            practice: None,
+        })
+    }
+
+    /** Give the holes listed without a stroke index the indexes left over,
+     * the longest of them the hardest. **/
+    fn index_holes(holes: &mut [Hole]) {
+        let taken: Vec<u32> = holes.iter().map(|hole| hole.handicap).collect();
+        let free = (1..).filter(|index| !taken.contains(index));
+        let unindexed: Vec<usize> = (0..holes.len())
+            .filter(|&i| holes[i].handicap == 0)
+            .sorted_by_key(|&i| std::cmp::Reverse(holes[i].yards))
+            .collect();
+        for (i, index) in unindexed.into_iter().zip(free) {
+            holes[i].handicap = index;
+        }
+    }
+
+    /** A round of generated holes that never ends. **/
+    pub fn practice(seed: u64, clubs: ClubSet) -> Self {
+        Self {
+            name: format!("Practice #{}", seed),
+            holes: Vec::new(),
+            round: Round::Full,
+            cursor: 0,
+            practice: Some(Practice::new(seed, clubs)),
+        }
+    }
+
+    /** Stands in for a course that could not be loaded. **/
+    pub fn empty() -> Self {
+        Self {
+            name: String::new(),
+            holes: Vec::new(),
+            round: Round::Full,
+            cursor: 0,
+            practice: None,
+        }
+    }
+
+    /** Every set of tees named on any hole, in the order they first appear. **/
+    pub fn tee_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for name in self.holes.iter().flat_map(|h| h.tee_names()) {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+
+    // indexes of the holes in the round
+    fn range(&self) -> Range<usize> {
+        let last = if self.practice.is_some() {
+            usize::MAX
+        } else {
+            self.holes.len()
+        };
+        match self.round {
+            Round::Front => 0..last.min(Course::NINE),
+            Round::Back => Course::NINE.min(last)..last,
+            Round::Full => 0..last,
+        }
+    }
+
+    pub fn round(&self) -> Round {
+        self.round
+    }
+
+    /** Play the next selection of holes the course has enough holes for. **/
+    pub fn next_round(&mut self) {
+        self.round = self.round.next();
+        if self.range().is_empty() {
+            self.round = self.round.next();
+        }
+        self.restart();
+    }
+
+    /** Back to the first hole of the round. **/
+    pub fn restart(&mut self) {
+        self.cursor = self.range().start;
+    }
+
+    /** Start from the following hole of the round, wrapping to its first. **/
+    pub fn next_start(&mut self) {
+        let range = self.range();
+        let last = range.end.min(self.holes.len().max(range.start + 1));
+        self.cursor = if self.cursor + 1 < last {
+            self.cursor + 1
+        } else {
+            range.start
+        };
+    }
+
+    /** Stroke indexes of the holes from the current one to the end of the
+     * round; none in practice, which never ends. **/
+    pub fn stroke_indexes(&self) -> Option<Vec<u32>> {
+        match self.practice {
+            Some(_) => None,
+            None => Some(
+                self.holes
+                    .get(self.cursor..self.range().end)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|hole| hole.handicap)
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn hole_number(&self) -> usize {
+        self.cursor + 1
+    }
+
+    /** The hole the cursor is on, generated first in practice. **/
+    pub fn current(&mut self) -> Option<Hole> {
+        if let Some(practice) = &mut self.practice {
+            while self.holes.len() <= self.cursor {
+                self.holes.push(practice.next_hole());
+            }
+        }
+        if self.range().contains(&self.cursor) {
+            self.holes.get(self.cursor).cloned()
+        } else {
+            None
+        }
+    }
+
+    /** Keep the changes made to a copy of one of the course's holes, as the
+     * editor makes them, for whenever it is next played. **/
+    pub fn update(&mut self, hole: &Hole) {
+        if let Some(kept) = self.holes.iter_mut().find(|h| h.number == hole.number) {
+            *kept = hole.clone();
+        }
+    }
+
+    /** Move on to the next hole; `None` once the round is over. **/
+    pub fn advance(&mut self) -> Option<Hole> {
+        self.cursor += 1;
+        self.current()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COURSE: &str = r#"
+name = "Test Links"
+
+[[holes]]
+name = "Short"
+par = 3
+handicap = 1
+grid = """
+..F..
+.....
+.....
+..T..
+"""
+
+[[holes.tees]]
+name = "Back"
+x = 2
+y = 3
+
+[[holes.pins]]
+name = "Middle"
+x = 2
+y = 0
+"#;
+
+    fn load_text(name: &str, text: &str) -> Result<Course, CourseError> {
+        let path = std::env::temp_dir().join(name);
+        fs::write(&path, text).unwrap();
+        Course::load(path.to_str().unwrap())
+    }
+
+    #[test]
+    fn a_manifest_parses_its_holes_and_markers() {
+        let manifest: CourseManifest = toml::from_str(COURSE).unwrap();
+        assert_eq!(manifest.name, "Test Links");
+        assert_eq!(manifest.holes.len(), 1);
+        let hole = &manifest.holes[0];
+        assert_eq!((hole.par, hole.handicap), (3, Some(1)));
+        assert_eq!(hole.tees[0].name, "Back");
+        assert_eq!((hole.pins[0].x, hole.pins[0].y), (2, 0));
+    }
+
+    #[test]
+    fn a_course_loads_from_its_manifest() {
+        let mut course = load_text("test_links.toml", COURSE).unwrap();
+        assert_eq!(course.name, "Test Links");
+        let hole = course.current().unwrap();
+        assert_eq!(hole.map.tee, Point::new(2, 3));
+        // its grid is written inline, so the editor has no file to save to
+        assert_eq!(Editor::new(&hole).file, None);
+    }
+
+    #[test]
+    fn holes_without_a_stroke_index_get_the_ones_left_longest_first() {
+        let short = COURSE.replace("handicap = 1\n", "");
+        let hole = &short[short.find("[[holes]]").unwrap()..];
+        // a short second hole and a longer third
+        let text = format!("{}{}{}", COURSE, hole.replace("y = 3", "y = 1"), hole);
+        let course = load_text("unindexed_links.toml", &text).unwrap();
+        assert_eq!(course.stroke_indexes(), Some(vec![1, 3, 2]));
+    }
+
+    #[test]
+    fn edits_to_a_hole_are_kept_by_the_course() {
+        let mut course = load_text("edited_links.toml", COURSE).unwrap();
+        let mut hole = course.current().unwrap();
+        hole.map.paint(&Point::new(0, 1), MapTile::Water);
+        course.update(&hole);
+        let replayed = course.current().unwrap();
+        assert_eq!(replayed.map.tile_at(&Point::new(0, 1)), MapTile::Water);
+    }
+
+    #[test]
+    fn the_flag_is_drawn_at_the_pin_played() {
+        let text = COURSE.replace("x = 2\ny = 0", "x = 3\ny = 1");
+        let mut course = load_text("pinned_links.toml", &text).unwrap();
+        let map = course.current().unwrap().map;
+        assert_eq!(map.shown_at(&Point::new(3, 1)), MapTile::Flag);
+        assert_eq!(map.shown_at(&Point::new(2, 0)), MapTile::Green);
+    }
+
+    #[test]
+    fn the_editor_moves_the_marker_in_play() {
+        let mut course = load_text("moved_links.toml", COURSE).unwrap();
+        let mut hole = course.current().unwrap();
+        assert_eq!(hole.move_tee(&Point::new(1, 2)), Some("Back".to_string()));
+        assert_eq!(
+            hole.move_flag(&Point::new(3, 0)),
+            Some("Middle".to_string())
+        );
+        // the grid keeps its own tee and flag
+        assert_eq!(hole.map.tile_at(&Point::new(2, 3)), MapTile::Tee);
+        assert_eq!(hole.map.tile_at(&Point::new(2, 0)), MapTile::Flag);
+        course.update(&hole);
+        let mut replayed = course.current().unwrap();
+        replayed.place(&Setup::new(course.tee_names()));
+        assert_eq!(replayed.map.tee, Point::new(1, 2));
+        assert_eq!(replayed.map.flag, Point::new(3, 0));
+    }
+
+    #[test]
+    fn a_map_file_is_found_beside_its_course_file() {
+        let folder = std::env::temp_dir().join("folder_links");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("short.txt"), "..F..\n.....\n.....\n..T..\n").unwrap();
+        let grid = &COURSE[COURSE.find("grid").unwrap()..COURSE.find("[[holes.tees]]").unwrap()];
+        let text = COURSE.replace(grid, "map = \"short.txt\"\n\n");
+        let path = folder.join("links.toml");
+        fs::write(&path, text).unwrap();
+        let mut course = Course::load(path.to_str().unwrap()).unwrap();
+        let hole = course.current().unwrap();
+        assert_eq!(hole.map.height, 4);
+        assert_eq!(
+            hole.source,
+            folder.join("short.txt").to_str().map(String::from)
         );
-        Self { holes }
     }
 
-    pub fn next(&mut self) -> Option<Hole> {
-        self.holes.pop_front()
+    #[test]
+    fn a_marker_off_the_map_is_rejected() {
+        let text = COURSE.replace("x = 2\ny = 0", "x = 2\ny = 40");
+        match load_text("off_map_links.toml", &text) {
+            Err(CourseError::OffMap { hole, marker }) => {
+                assert_eq!(hole, 1);
+                assert_eq!(marker.name, "Middle");
+            }
+            Err(other) => panic!("expected a marker off the map, got {}", other),
+            Ok(_) => panic!("expected a marker off the map"),
+        }
     }
 }
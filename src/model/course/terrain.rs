@@ -0,0 +1,27 @@
+use serde::Deserialize;
+
+/** How deep rough is cut back around the tiles that feed it: the first cut
+ * hugs them, the second cut (plain rough) spreads out past it. **/
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Smoothing {
+    /// map characters of the tiles rough is cut around
+    pub feeders: Vec<char>,
+    /// how far, in tiles, a deep rough tile looks for feeders
+    pub radius: i32,
+    /// deep rough with more feeders than this in range becomes rough
+    pub threshold: usize,
+    /// deep rough this close to a feeder becomes first cut; 0 leaves none
+    pub first_cut: i32,
+}
+
+impl Default for Smoothing {
+    fn default() -> Self {
+        Smoothing {
+            feeders: vec!['='],
+            radius: 8,
+            threshold: 3,
+            first_cut: 0,
+        }
+    }
+}
@@ -1,11 +1,11 @@
 pub struct Window {
-    pub height: u8,
-    pub width: u8,
+    pub height: u16,
+    pub width: u16,
 }
 
 impl Window {
-    const SCREEN_HEIGHT: u8 = 60;
-    const SCREEN_WIDTH: u8 = 80;
+    const SCREEN_HEIGHT: u16 = 60;
+    const SCREEN_WIDTH: u16 = 80;
 
     pub fn new() -> Self {
         Self {
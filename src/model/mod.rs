@@ -1,19 +1,27 @@
 pub mod ball;
 pub mod camera;
 pub mod club;
+pub mod computer;
 pub mod course;
 mod frame_time;
 pub mod interface;
+mod notice;
+pub mod player;
 pub mod state;
 pub mod tile;
 mod util;
+pub mod wind;
 
 pub use ball::Ball;
 pub use camera::*;
 pub use club::*;
+pub use computer::Computer;
 pub use course::*;
 pub use frame_time::*;
 pub use interface::*;
 pub use map::Map;
+pub use notice::Notice;
+pub use player::{Active, Player};
 pub use state::*;
-pub use tile::MapTile;
+pub use tile::{MapTile, Surface};
+pub use wind::Wind;
@@ -0,0 +1,33 @@
+/** A message flashed in the bottom panel for a few seconds. **/
+pub struct Notice {
+    message: String,
+    seconds_left: f32,
+}
+
+impl Notice {
+    const DURATION: f32 = 3.;
+
+    pub fn new() -> Self {
+        Notice {
+            message: String::new(),
+            seconds_left: 0.,
+        }
+    }
+
+    pub fn show(&mut self, message: &str) {
+        self.message = message.to_string();
+        self.seconds_left = Notice::DURATION;
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        self.seconds_left = (self.seconds_left - dt).max(0.);
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        if self.seconds_left > 0. {
+            Some(&self.message)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::prelude::*;
+
+/** A golfer taking turns at the keyboard, with their own bag and their
+ * strokes on the hole being played. **/
+pub struct Player {
+    pub number: usize,
+    pub name: String,
+    pub clubs: ClubSet,
+    /// strokes so far this hole, kept here while another player is up
+    pub hole_state: HoleState,
+    /// place on the next tee, lowest first, from the last hole's scores
+    pub honor: usize,
+}
+
+/** Marks the player whose turn it is; their ball is the one in play. **/
+pub struct Active;
+
+impl Player {
+    pub const MAX_PLAYERS: usize = 4;
+    const BALL_COLORS: [(u8, u8, u8); Player::MAX_PLAYERS] = [WHITE, YELLOW, CYAN, ORANGE];
+
+    pub fn new(number: usize, clubs: ClubSet) -> Self {
+        Player {
+            number,
+            name: format!("Player {}", number + 1),
+            clubs,
+            hole_state: HoleState::new(),
+            honor: number,
+        }
+    }
+
+    pub fn color(&self) -> (u8, u8, u8) {
+        Player::BALL_COLORS[self.number % Player::MAX_PLAYERS]
+    }
+
+    pub fn holed(&self) -> bool {
+        matches!(self.hole_state, HoleState::Holed(_, _))
+    }
+
+    /** Spawn `count` golfers, each on `tee` with a copy of `clubs`; the first
+     * has the honor, and the last `computers` of them are the computer's. **/
+    pub fn spawn_all(
+        commands: &mut Commands,
+        count: usize,
+        computers: usize,
+        tee: &Point,
+        clubs: &ClubSet,
+    ) {
+        let count = count.clamp(1, Player::MAX_PLAYERS);
+        for number in 0..count {
+            let mut player = Player::new(number, clubs.clone());
+            let computer = number + computers >= count;
+            if computer {
+                player.name = format!("CPU {}", number + 1);
+            }
+            commands.spawn((Ball::new(tee), player));
+            if computer {
+                commands.with(Computer::new(Computer::SKILLS[number]));
+            }
+            if number == 0 {
+                commands.with(Active);
+            }
+        }
+    }
+}
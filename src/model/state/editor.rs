@@ -0,0 +1,52 @@
+use crate::prelude::*;
+
+/** Where the hole editor's cursor is, what it paints, and where the grid is saved. **/
+pub struct Editor {
+    pub hole: usize,
+    pub cursor: Point,
+    // the hole's map file; a grid written in the course file, or a generated
+    // hole, has none to save to
+    pub file: Option<String>,
+    brush: usize,
+}
+
+impl Editor {
+    const PALETTE: [MapTile; 11] = [
+        MapTile::DeepRough,
+        MapTile::Rough,
+        MapTile::FirstCut,
+        MapTile::Fairway,
+        MapTile::Green,
+        MapTile::TeeBox,
+        MapTile::Bunker,
+        MapTile::Water,
+        MapTile::Tree,
+        MapTile::Bush,
+        MapTile::OutOfBounds,
+    ];
+
+    pub fn new(hole: &Hole) -> Self {
+        Editor {
+            hole: hole.number,
+            cursor: hole.map.tee,
+            file: hole.source.clone(),
+            brush: 0,
+        }
+    }
+
+    pub fn brush(&self) -> MapTile {
+        Editor::PALETTE[self.brush]
+    }
+
+    pub fn next_brush(&mut self) {
+        self.brush = (self.brush + 1) % Editor::PALETTE.len();
+    }
+
+    /** Step the cursor, keeping it on the map. **/
+    pub fn move_cursor(&mut self, step: Point, map: &Map) {
+        let moved = self.cursor + step;
+        if map.in_bounds(&moved) {
+            self.cursor = moved;
+        }
+    }
+}
@@ -1,8 +1,44 @@
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HoleOut {
+    HoleInOne,
+    ChipIn,
+    HoledOut,
+    Putt,
+    Conceded,
+}
+
+impl HoleOut {
+    /** Tiles from the green a holed shot may be played from and still be a chip. **/
+    pub const CHIP_REACH: i32 = 3;
+
+    pub fn of(strokes: u32, putt: bool, near_green: bool) -> Self {
+        if strokes == 1 {
+            HoleOut::HoleInOne
+        } else if putt {
+            HoleOut::Putt
+        } else if near_green {
+            HoleOut::ChipIn
+        } else {
+            HoleOut::HoledOut
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            HoleOut::HoleInOne => "Hole in one!",
+            HoleOut::ChipIn => "Chip-in!",
+            HoleOut::HoledOut => "Holed out!",
+            HoleOut::Putt => "In the hole!",
+            HoleOut::Conceded => "Putt conceded.",
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum HoleState {
     TeeOff,
     Stroke(u32),
-    Holed,
+    Holed(u32, HoleOut),
 }
 
 impl HoleState {
@@ -20,4 +56,52 @@ impl HoleState {
             _other => (),
         }
     }
+
+    pub fn penalty(&mut self, strokes: u32) {
+        if let HoleState::Stroke(taken) = self {
+            *taken += strokes;
+        }
+    }
+
+    pub fn hole_out(&mut self, putt: bool, near_green: bool) {
+        if let HoleState::Stroke(strokes) = *self {
+            *self = HoleState::Holed(strokes, HoleOut::of(strokes, putt, near_green));
+        }
+    }
+
+    /** The putt is given: it counts, but need not be struck. **/
+    pub fn concede(&mut self) {
+        if let HoleState::Stroke(strokes) = *self {
+            *self = HoleState::Holed(strokes + 1, HoleOut::Conceded);
+        }
+    }
+
+    pub fn strokes(&self) -> u32 {
+        match self {
+            HoleState::TeeOff => 0,
+            HoleState::Stroke(strokes) | HoleState::Holed(strokes, _) => *strokes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_shot_from_near_the_green_is_a_chip_in() {
+        assert_eq!(HoleOut::of(1, false, false), HoleOut::HoleInOne);
+        assert_eq!(HoleOut::of(3, true, true), HoleOut::Putt);
+        assert_eq!(HoleOut::of(3, false, true), HoleOut::ChipIn);
+        assert_eq!(HoleOut::of(2, false, false), HoleOut::HoledOut);
+    }
+
+    #[test]
+    fn a_conceded_putt_counts_a_stroke() {
+        let mut state = HoleState::start_hole();
+        state.increment();
+        state.increment();
+        state.concede();
+        assert_eq!(state.strokes(), 3);
+    }
 }
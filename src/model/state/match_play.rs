@@ -0,0 +1,225 @@
+use crate::prelude::*;
+
+/** A match between two sides, odd and even players, each counting its
+ * best ball on a hole; a lone golfer plays against par. **/
+pub struct Match {
+    golfers: usize,
+    // holes in the round, none for a practice round that never ends
+    holes: Option<usize>,
+    // the side that won each hole played, none where it was halved
+    results: Vec<Option<usize>>,
+}
+
+impl Match {
+    /** Short putts, in meters from the cup, the other side may concede. **/
+    pub const CONCEDE_DISTANCE: f32 = 1.;
+
+    pub fn new(golfers: usize, holes: Option<usize>) -> Self {
+        Match {
+            golfers,
+            holes,
+            results: Vec::new(),
+        }
+    }
+
+    /** Close enough to the hole on the green for the putt to be given. **/
+    pub fn gimme(map: &Map, ball: &Ball) -> bool {
+        let pos = ball.tile_position();
+        map.in_bounds(&pos)
+            && map.tile_at(&pos).surface().stimp.is_some()
+            && ball.distance_to(&map.flag) * Map::METERS_PER_TILE <= Match::CONCEDE_DISTANCE
+    }
+
+    /** Only the other side gives a putt, and only a person on it: `humans`
+     * are the players the computer does not play for. **/
+    pub fn may_concede(player: usize, humans: &[usize]) -> bool {
+        humans
+            .iter()
+            .any(|&human| Match::side(human) != Match::side(player))
+    }
+
+    pub fn side(player: usize) -> usize {
+        player % 2
+    }
+
+    pub fn side_name(&self, side: usize) -> String {
+        if self.golfers == 1 && side == 1 {
+            return "Par".to_string();
+        }
+        (0..self.golfers)
+            .filter(|&player| Match::side(player) == side)
+            .map(|player| format!("P{}", player + 1))
+            .join(" & ")
+    }
+
+    // a side's best ball, once all of the side has a score for the hole
+    fn best_ball(&self, scorecard: &Scorecard, side: usize, hole: &HoleScore) -> Option<u32> {
+        if self.golfers == 1 && side == 1 {
+            return Some(hole.par);
+        }
+        let scores: Option<Vec<u32>> = (0..self.golfers)
+            .filter(|&player| Match::side(player) == side)
+            .map(|player| scorecard.score(player, hole.number).map(|s| s.strokes))
+            .collect();
+        scores.and_then(|scores| scores.into_iter().min())
+    }
+
+    // holes up for the first side, and holes left to play
+    fn standing(&self) -> (i32, Option<usize>) {
+        let up: i32 = self
+            .results
+            .iter()
+            .map(|result| match result {
+                Some(0) => 1,
+                Some(_) => -1,
+                None => 0,
+            })
+            .sum();
+        let left = self
+            .holes
+            .map(|holes| holes.saturating_sub(self.results.len()));
+        (up, left)
+    }
+
+    /** The match as a caddie would put it: who leads, then what is left. **/
+    fn describe(&self) -> (String, String) {
+        let (up, left) = self.standing();
+        let leader = self.side_name(if up > 0 { 0 } else { 1 });
+        let lead = up.unsigned_abs() as usize;
+        match left {
+            Some(0) if lead == 0 => ("Match".to_string(), "halved".to_string()),
+            Some(0) => (format!("{} wins", leader), format!("{} UP", lead)),
+            Some(left) if lead > left => {
+                (format!("{} wins", leader), format!("{} & {}", lead, left))
+            }
+            Some(left) if lead == 0 => ("All square".to_string(), format!("{} to play", left)),
+            Some(left) if lead == left => (format!("{} {} UP", leader, lead), "Dormie".to_string()),
+            Some(left) => (
+                format!("{} {} UP", leader, lead),
+                format!("{} to play", left),
+            ),
+            None if lead == 0 => ("All square".to_string(), String::new()),
+            None => (format!("{} {} UP", leader, lead), String::new()),
+        }
+    }
+}
+
+impl ScoringMode for Match {
+    fn name(&self) -> &'static str {
+        "Match play"
+    }
+
+    /** Won, halved or lost on the better ball of each side. **/
+    fn record(&mut self, scorecard: &Scorecard, number: usize) {
+        let hole = match scorecard.holes().find(|hole| hole.number == number) {
+            Some(hole) => hole,
+            None => return,
+        };
+        let ours = self.best_ball(scorecard, 0, hole);
+        let theirs = self.best_ball(scorecard, 1, hole);
+        if let (Some(ours), Some(theirs)) = (ours, theirs) {
+            self.results.push(match ours.cmp(&theirs) {
+                std::cmp::Ordering::Less => Some(0),
+                std::cmp::Ordering::Greater => Some(1),
+                std::cmp::Ordering::Equal => None,
+            });
+        }
+    }
+
+    fn results(&self) -> Vec<String> {
+        let (leader, margin) = self.describe();
+        vec![format!("{} {}", leader, margin)]
+    }
+
+    fn status(&self) -> Option<(String, String)> {
+        Some(self.describe())
+    }
+
+    /** Over once the side behind could no longer draw level. **/
+    fn decided(&self) -> bool {
+        match self.standing() {
+            (up, Some(left)) => up.unsigned_abs() as usize > left,
+            (_, None) => false,
+        }
+    }
+
+    /** A lone golfer playing par has nobody to give a putt. **/
+    fn concessions(&self) -> bool {
+        self.golfers > 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a match of two golfers over `holes`, with the first side winning the
+    // first `won` holes and halving the rest of those `played`
+    fn after(holes: usize, played: usize, won: usize) -> Match {
+        let mut game = Match::new(2, Some(holes));
+        game.results = (0..played)
+            .map(|i| if i < won { Some(0) } else { None })
+            .collect();
+        game
+    }
+
+    fn status(game: &Match) -> (String, String) {
+        game.status().unwrap()
+    }
+
+    #[test]
+    fn the_match_is_called_as_it_stands() {
+        assert_eq!(
+            status(&after(18, 0, 0)),
+            ("All square".to_string(), "18 to play".to_string())
+        );
+        assert_eq!(
+            status(&after(18, 10, 1)),
+            ("P1 1 UP".to_string(), "8 to play".to_string())
+        );
+        assert_eq!(
+            status(&after(18, 16, 2)),
+            ("P1 2 UP".to_string(), "Dormie".to_string())
+        );
+    }
+
+    #[test]
+    fn a_match_won_early_counts_the_holes_left() {
+        let game = after(18, 16, 3);
+        assert!(game.decided());
+        assert_eq!(status(&game), ("P1 wins".to_string(), "3 & 2".to_string()));
+        assert!(!after(18, 16, 2).decided());
+    }
+
+    #[test]
+    fn a_match_level_at_the_end_is_halved() {
+        assert_eq!(
+            status(&after(9, 9, 0)),
+            ("Match".to_string(), "halved".to_string())
+        );
+    }
+
+    #[test]
+    fn only_a_putt_inside_a_meter_is_a_gimme() {
+        let map = Map::parse(&["..F..".to_string(), "..T..".to_string()], None).unwrap();
+        let mut ball = Ball::new(&Point::new(3, 0));
+        assert!(!Match::gimme(&map, &ball), "on the next tile");
+        // to the middle of the cup's tile, then a little short of it
+        ball.mv(135., 0.5_f32.sqrt());
+        ball.mv(0., 0.1);
+        assert!(Match::gimme(&map, &ball));
+        ball.mv(0., 0.1);
+        assert!(!Match::gimme(&map, &ball));
+    }
+
+    #[test]
+    fn putts_are_given_only_by_a_person_on_the_other_side() {
+        assert!(!Match::new(1, None).concessions());
+        assert!(Match::new(2, None).concessions());
+        assert!(!Match::may_concede(0, &[0]));
+        assert!(Match::may_concede(0, &[1]));
+        // partners cannot give each other putts
+        assert!(!Match::may_concede(2, &[0, 2]));
+        assert!(Match::may_concede(2, &[0, 3]));
+    }
+}
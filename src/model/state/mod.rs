@@ -1,5 +1,15 @@
+pub mod editor;
 pub mod hole;
+pub mod match_play;
+pub mod planner;
+pub mod scorecard;
+pub mod scoring;
 pub mod turn;
 
+pub use editor::*;
 pub use hole::*;
+pub use match_play::*;
+pub use planner::*;
+pub use scorecard::*;
+pub use scoring::*;
 pub use turn::*;
@@ -0,0 +1,266 @@
+use crate::prelude::*;
+
+/** A shot as it is meant to be played: the club, the line, and where to
+ * stop the power bar and the accuracy cursor. **/
+#[derive(Copy, Clone, Debug)]
+pub struct Shot {
+    pub club: usize,
+    pub aim: Aim,
+    pub power: f32,
+    pub accuracy: f32,
+}
+
+// where a simulated shot comes to rest
+enum Landing {
+    Holed,
+    Penalty,
+    Blocked,
+    At(Point),
+}
+
+/** Plays candidate shots through the `Travel` model over the map and picks
+ * the one that finishes nearest the hole on the kindest lie. **/
+pub struct Planner<'a> {
+    map: &'a Map,
+    clubs: &'a ClubSet,
+    wind: &'a Wind,
+}
+
+impl<'a> Planner<'a> {
+    // a minute of flight, a `Travel::STEP` at a time
+    const MAX_STEPS: usize = 900;
+    // degrees either side of the flag worth trying, off the green and on it
+    const SPREAD: f32 = 30.;
+    const PUTT_SPREAD: f32 = 12.;
+    // aim steps between the lines of the first, coarse search
+    const COARSE_AIM: i32 = 2;
+    // meter points between powers tried in the coarse search, full shots and putts;
+    // the search then goes over the best of them again in steps a quarter as big
+    const COARSE_POWER: f32 = 20.;
+    const COARSE_PUTT_POWER: f32 = 10.;
+    // how many clubs to try, those whose full shot best fits the distance
+    const CLUBS_TRIED: usize = 3;
+    const PENALTY_COST: f32 = 1000.;
+
+    pub fn new(map: &'a Map, clubs: &'a ClubSet, wind: &'a Wind) -> Self {
+        Planner { map, clubs, wind }
+    }
+
+    /** The best shot found from where `ball` lies: a coarse search over
+     * clubs, lines and powers, then a finer one around the best of those. **/
+    pub fn plan(&self, ball: &Ball) -> Shot {
+        let from = ball.tile_position();
+        let lie = self.lie(&from);
+        let to_flag = self.map.flag - from;
+        let distance = ((to_flag.x.pow(2) + to_flag.y.pow(2)) as f32).sqrt();
+        // aim turns in steps from straight up the screen, as the keys move it
+        let bearing = (-to_flag.y as f32).atan2(to_flag.x as f32).to_degrees() - 90.;
+        let bearing = (bearing / Aim::RATE).round() * Aim::RATE;
+        let bearing = if bearing < -180. {
+            bearing + 360.
+        } else {
+            bearing
+        };
+        let (clubs, spread, power_step) = if lie.stimp.is_some() {
+            (
+                vec![self.clubs.default_for(&lie)],
+                Planner::PUTT_SPREAD,
+                Planner::COARSE_PUTT_POWER,
+            )
+        } else {
+            (
+                self.clubs_for(distance, &lie),
+                Planner::SPREAD,
+                Planner::COARSE_POWER,
+            )
+        };
+        let steps = (spread / Aim::RATE) as i32;
+        let aims: Vec<i32> = (-steps..=steps)
+            .step_by(Planner::COARSE_AIM as usize)
+            .collect();
+        let powers: Vec<f32> = (1..=(100. / power_step) as i32)
+            .map(|p| p as f32 * power_step)
+            .collect();
+        let coarse = clubs
+            .iter()
+            .cartesian_product(aims.iter())
+            .cartesian_product(powers.iter())
+            .map(|((&club, &aim), &power)| (club, aim, power));
+        let (lowest, best) = match self.best(ball, &lie, bearing, coarse) {
+            Some(found) => found,
+            None => {
+                return Shot {
+                    club: self.clubs.default_for(&lie),
+                    aim: Aim::of(bearing),
+                    power: 100.,
+                    accuracy: 0.,
+                }
+            }
+        };
+        // and again, closer in, around the best of the coarse shots
+        let aim = ((best.aim.degrees - bearing) / Aim::RATE).round() as i32;
+        let fine_step = power_step / 4.;
+        let fine = (aim - Planner::COARSE_AIM + 1..Planner::COARSE_AIM + aim)
+            .cartesian_product(-3..=3)
+            .map(|(aim, p)| {
+                let power = (best.power + p as f32 * fine_step).max(fine_step).min(100.);
+                (best.club, aim, power)
+            });
+        match self.best(ball, &lie, bearing, fine) {
+            Some((cost, shot)) if cost < lowest => shot,
+            _ => best,
+        }
+    }
+
+    // the lowest costing of `candidates`, each a club, aim steps off `bearing` and power
+    fn best(
+        &self,
+        ball: &Ball,
+        lie: &Surface,
+        bearing: f32,
+        candidates: impl Iterator<Item = (usize, i32, f32)>,
+    ) -> Option<(f32, Shot)> {
+        let mut best: Option<(f32, Shot)> = None;
+        for (club, step, power) in candidates {
+            let shot = Shot {
+                club,
+                aim: Aim::of(bearing + step as f32 * Aim::RATE),
+                power,
+                accuracy: 0.,
+            };
+            let cost = self.cost(self.simulate(ball, &shot, lie));
+            if best.is_none_or(|(lowest, _)| cost < lowest) {
+                best = Some((cost, shot));
+            }
+        }
+        best
+    }
+
+    fn lie(&self, point: &Point) -> Surface {
+        if self.map.in_bounds(point) {
+            self.map.tile_at(point).surface()
+        } else {
+            Surface::DEEP_ROUGH
+        }
+    }
+
+    // the clubs whose full shot from `lie` comes closest to `distance` tiles
+    fn clubs_for(&self, distance: f32, lie: &Surface) -> Vec<usize> {
+        self.clubs
+            .iter()
+            .enumerate()
+            .map(|(i, club)| (i, (Travel::full_distance(club, lie) - distance).abs()))
+            .sorted_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .take(Planner::CLUBS_TRIED)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /** Follow `shot` the way the turn handler would, without the luck of
+     * glancing off an obstacle. **/
+    fn simulate(&self, ball: &Ball, shot: &Shot, lie: &Surface) -> Landing {
+        let mut ball = *ball;
+        ball.strike();
+        let club = self.clubs.at(&shot.club);
+        let mut travel = Travel::new(&shot.power, &shot.accuracy, &shot.aim, club, lie);
+        for _ in 0..Planner::MAX_STEPS {
+            if travel.finished() {
+                break;
+            }
+            let from = ball;
+            ball.mv(travel.direction, travel.tile_distance(Travel::STEP));
+            ball.mv(
+                travel.direction - 90.,
+                travel.lateral_distance(Travel::STEP),
+            );
+            let pos = ball.tile_position();
+            if !self.map.in_bounds(&pos) {
+                return Landing::Penalty;
+            }
+            let tile = self.map.tile_at(&pos);
+            let ground = self.map.ground_at(&pos, &ball.lie_position());
+            let crossed = self
+                .map
+                .crossed(&from.tile_position(), &pos, &ball.lie_position());
+            if crossed
+                .iter()
+                .any(|(tile, ground)| travel.pass_over(tile.obstacle_height(), ground))
+            {
+                return Landing::Blocked;
+            }
+            travel.tick(Travel::STEP, &ground, self.wind);
+            if travel.on_ground() && tile.is_hazard() {
+                return Landing::Penalty;
+            }
+            // try the cup on a copy, so a lip-out doesn't bend this path
+            let mut probe = travel;
+            if ball.closest_approach(&from, &self.map.flag) <= Travel::CUP_RADIUS
+                && probe.reach_cup() == Cup::Drop
+            {
+                return Landing::Holed;
+            }
+        }
+        Landing::At(ball.tile_position())
+    }
+
+    fn cost(&self, landing: Landing) -> f32 {
+        match landing {
+            Landing::Holed => -Planner::PENALTY_COST,
+            Landing::Penalty => Planner::PENALTY_COST,
+            Landing::Blocked => Planner::PENALTY_COST / 2.,
+            Landing::At(pos) => {
+                DistanceAlg::Pythagoras.distance2d(pos, self.map.flag)
+                    + Planner::lie_cost(self.map.tile_at(&pos))
+            }
+        }
+    }
+
+    /** Tiles of distance a lie is worth giving up to stay out of it. **/
+    fn lie_cost(tile: MapTile) -> f32 {
+        match tile {
+            MapTile::FirstCut => 2.,
+            MapTile::Rough => 6.,
+            MapTile::Bunker => 10.,
+            MapTile::DeepRough | MapTile::Tree | MapTile::Bush => 15.,
+            MapTile::Water | MapTile::OutOfBounds => Planner::PENALTY_COST,
+            _ => 0.,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_plan_stays_out_of_the_water() {
+        let clubs = ClubSet::load(ClubSet::DEFAULT_FILE).unwrap();
+        let still = Wind::of(0., 0., 0.);
+        // a lake across the hole where the straight drive would come down
+        let mut rows = vec!["=========F=========".to_string()];
+        rows.extend(vec!["===================".to_string(); 6]);
+        rows.extend(vec!["~~~~~~~~~~~~~~~~~~~".to_string(); 12]);
+        rows.extend(vec!["===================".to_string(); 20]);
+        rows.push("=========T=========".to_string());
+        let map = Map::parse(&rows, None).unwrap();
+        let planner = Planner::new(&map, &clubs, &still);
+        let ball = Ball::new(&map.tee);
+        let driver = Shot {
+            club: 0,
+            aim: Aim::new(),
+            power: 100.,
+            accuracy: 0.,
+        };
+        let tee = Surface::TEE_BOX;
+        assert!(matches!(
+            planner.simulate(&ball, &driver, &tee),
+            Landing::Penalty
+        ));
+        let shot = planner.plan(&ball);
+        match planner.simulate(&ball, &shot, &tee) {
+            Landing::At(pos) => assert!(!map.tile_at(&pos).is_hazard()),
+            Landing::Holed => {}
+            Landing::Penalty | Landing::Blocked => panic!("planned {:?} into trouble", shot),
+        }
+    }
+}
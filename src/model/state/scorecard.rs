@@ -0,0 +1,109 @@
+use crate::prelude::*;
+
+#[derive(Clone, Debug)]
+pub struct HoleScore {
+    pub player: usize,
+    pub number: usize,
+    pub name: String,
+    pub par: u32,
+    /// the hole's stroke index, for handicap strokes
+    pub handicap: u32,
+    pub strokes: u32,
+}
+
+impl HoleScore {
+    pub fn to_par(&self) -> i32 {
+        self.strokes as i32 - self.par as i32
+    }
+
+    /** The golfer's name for a score, from albatross to double bogey and worse. **/
+    pub fn describe(&self) -> &'static str {
+        match self.to_par() {
+            diff if diff <= -3 => "Albatross",
+            -2 => "Eagle",
+            -1 => "Birdie",
+            0 => "Par",
+            1 => "Bogey",
+            2 => "Double Bogey",
+            _ => "Triple Bogey+",
+        }
+    }
+}
+
+/** Strokes each player took on each hole of the round, in hole order. **/
+pub struct Scorecard {
+    scores: Vec<HoleScore>,
+}
+
+impl Scorecard {
+    pub fn new() -> Self {
+        Scorecard { scores: Vec::new() }
+    }
+
+    /** Score `hole` for `player`, replacing any earlier score for it from a
+     * replay. **/
+    pub fn record(&mut self, player: usize, hole: &Hole, strokes: u32) {
+        self.scores
+            .retain(|s| (s.player, s.number) != (player, hole.number));
+        self.scores.push(HoleScore {
+            player,
+            number: hole.number,
+            name: hole.name.clone(),
+            par: hole.par,
+            handicap: hole.handicap,
+            strokes,
+        });
+        self.scores.sort_by_key(|s| (s.number, s.player));
+    }
+
+    /** The holes played so far, one score each, in order. **/
+    pub fn holes(&self) -> impl Iterator<Item = &HoleScore> {
+        self.scores.iter().dedup_by(|a, b| a.number == b.number)
+    }
+
+    /** Everyone with a score on the card, in playing order. **/
+    pub fn players(&self) -> Vec<usize> {
+        self.scores
+            .iter()
+            .map(|s| s.player)
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    pub fn score(&self, player: usize, number: usize) -> Option<&HoleScore> {
+        self.scores
+            .iter()
+            .find(|s| (s.player, s.number) == (player, number))
+    }
+
+    fn of(&self, player: usize) -> impl Iterator<Item = &HoleScore> {
+        self.scores.iter().filter(move |s| s.player == player)
+    }
+
+    pub fn strokes(&self, player: usize) -> u32 {
+        self.of(player).map(|s| s.strokes).sum()
+    }
+
+    pub fn par(&self, player: usize) -> u32 {
+        self.of(player).map(|s| s.par).sum()
+    }
+
+    pub fn to_par(&self, player: usize) -> i32 {
+        self.strokes(player) as i32 - self.par(player) as i32
+    }
+
+    /** How many holes `player` played at a score to par that is `wanted`. **/
+    pub fn count(&self, player: usize, wanted: impl Fn(i32) -> bool) -> usize {
+        self.of(player).filter(|s| wanted(s.to_par())).count()
+    }
+
+    /** Relative to par the way a leaderboard shows it: E, +3, -2. **/
+    pub fn relative(diff: i32) -> String {
+        match diff {
+            0 => "E".to_string(),
+            d if d > 0 => format!("+{}", d),
+            d => format!("{}", d),
+        }
+    }
+}
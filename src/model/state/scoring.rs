@@ -0,0 +1,360 @@
+use crate::prelude::*;
+
+/** Rules for turning each hole's strokes into a result for the round. **/
+pub trait ScoringMode: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /** Score hole `number` once everyone has holed out on it. **/
+    fn record(&mut self, scorecard: &Scorecard, number: usize);
+
+    /** Standings for the round summary, a line each. **/
+    fn results(&self) -> Vec<String>;
+
+    /** Two short lines on the state of play for the side panel. **/
+    fn status(&self) -> Option<(String, String)> {
+        None
+    }
+
+    /** Whether the round is over before its last hole. **/
+    fn decided(&self) -> bool {
+        false
+    }
+
+    /** Whether short putts may be given. **/
+    fn concessions(&self) -> bool {
+        false
+    }
+}
+
+/** The rules the round is being played under. **/
+pub struct Scoring(pub Box<dyn ScoringMode>);
+
+/** The formats on offer from the menu. **/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    StrokePlay,
+    MatchPlay,
+    Stableford,
+    Skins,
+}
+
+impl Format {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::StrokePlay => "Stroke play",
+            Format::MatchPlay => "Match play",
+            Format::Stableford => "Stableford",
+            Format::Skins => "Skins",
+        }
+    }
+
+    pub fn next(&self) -> Format {
+        match self {
+            Format::StrokePlay => Format::MatchPlay,
+            Format::MatchPlay => Format::Stableford,
+            Format::Stableford => Format::Skins,
+            Format::Skins => Format::StrokePlay,
+        }
+    }
+
+    /** Fresh rules for a round played by `golfers` over the holes with
+     * stroke `indexes`, or an endless one where there are none. **/
+    pub fn scoring(&self, golfers: usize, indexes: Option<&[u32]>, handicaps: &[u32]) -> Scoring {
+        Scoring(match self {
+            Format::StrokePlay => Box::new(StrokePlay::new(golfers)),
+            Format::MatchPlay => Box::new(Match::new(golfers, indexes.map(|i| i.len()))),
+            Format::Stableford => Box::new(Stableford::new(golfers, handicaps, indexes)),
+            Format::Skins => Box::new(Skins::new(golfers)),
+        })
+    }
+}
+
+/** Lowest total wins. **/
+pub struct StrokePlay {
+    totals: Vec<u32>,
+}
+
+impl StrokePlay {
+    pub fn new(golfers: usize) -> Self {
+        StrokePlay {
+            totals: vec![0; golfers],
+        }
+    }
+}
+
+impl ScoringMode for StrokePlay {
+    fn name(&self) -> &'static str {
+        "Stroke play"
+    }
+
+    fn record(&mut self, scorecard: &Scorecard, number: usize) {
+        for (player, total) in self.totals.iter_mut().enumerate() {
+            if let Some(score) = scorecard.score(player, number) {
+                *total += score.strokes;
+            }
+        }
+    }
+
+    fn results(&self) -> Vec<String> {
+        let best = self.totals.iter().min().copied().unwrap_or(0);
+        let leaders: Vec<usize> = (0..self.totals.len())
+            .filter(|&player| self.totals[player] == best)
+            .collect();
+        let runner_up = self.totals.iter().filter(|&&t| t > best).min();
+        match (leaders.as_slice(), runner_up) {
+            (_, _) if self.totals.len() < 2 => Vec::new(),
+            ([leader], Some(next)) => vec![format!(
+                "P{} wins by {} with {}",
+                leader + 1,
+                next - best,
+                best
+            )],
+            (leaders, _) => vec![format!(
+                "Tied on {}: {}",
+                best,
+                leaders.iter().map(|p| format!("P{}", p + 1)).join(", ")
+            )],
+        }
+    }
+}
+
+/** Points against par, net of the strokes each golfer's handicap gives
+ * them on the hardest holes: two for a net par, one more for each stroke
+ * better, one fewer for each worse, never below nothing. **/
+pub struct Stableford {
+    handicaps: Vec<u32>,
+    // stroke indexes of the round's holes; none for an endless round
+    indexes: Option<Vec<u32>>,
+    points: Vec<u32>,
+}
+
+impl Stableford {
+    // a handicap is the strokes given over this many holes
+    const HOLES: u32 = 18;
+
+    pub fn new(golfers: usize, handicaps: &[u32], indexes: Option<&[u32]>) -> Self {
+        Stableford {
+            handicaps: (0..golfers)
+                .map(|player| handicaps.get(player).copied().unwrap_or(0))
+                .collect(),
+            indexes: indexes.map(|indexes| indexes.to_vec()),
+            points: vec![0; golfers],
+        }
+    }
+
+    /** Strokes received on the hole with stroke index `index`: the
+     * handicap's share for the round's holes, given a stroke at a time from
+     * the hardest of them. An endless round is taken eighteen at a time. **/
+    fn strokes_received(&self, handicap: u32, index: u32) -> u32 {
+        let (allowance, holes, rank) = match &self.indexes {
+            Some(indexes) if !indexes.is_empty() => {
+                let holes = indexes.len() as u32;
+                let allowance = (handicap * holes + Stableford::HOLES / 2) / Stableford::HOLES;
+                let rank = indexes.iter().filter(|&&other| other < index).count() as u32 + 1;
+                (allowance, holes, rank)
+            }
+            _ => {
+                let rank = (index.max(1) - 1) % Stableford::HOLES + 1;
+                (handicap, Stableford::HOLES, rank)
+            }
+        };
+        allowance / holes + (rank <= allowance % holes) as u32
+    }
+
+    pub fn points(&self, score: &HoleScore, handicap: u32) -> u32 {
+        let allowed = score.par + 2 + self.strokes_received(handicap, score.handicap);
+        allowed.saturating_sub(score.strokes)
+    }
+
+    fn leader(&self) -> Option<usize> {
+        (0..self.points.len()).max_by_key(|&player| (self.points[player], usize::MAX - player))
+    }
+}
+
+impl ScoringMode for Stableford {
+    fn name(&self) -> &'static str {
+        "Stableford"
+    }
+
+    fn record(&mut self, scorecard: &Scorecard, number: usize) {
+        for player in 0..self.points.len() {
+            if let Some(score) = scorecard.score(player, number) {
+                self.points[player] += self.points(score, self.handicaps[player]);
+            }
+        }
+    }
+
+    fn results(&self) -> Vec<String> {
+        (0..self.points.len())
+            .sorted_by_key(|&player| std::cmp::Reverse(self.points[player]))
+            .map(|player| {
+                format!(
+                    "P{} {} points (hcp {})",
+                    player + 1,
+                    self.points[player],
+                    self.handicaps[player]
+                )
+            })
+            .collect()
+    }
+
+    fn status(&self) -> Option<(String, String)> {
+        self.leader().map(|player| {
+            (
+                format!("P{} leads", player + 1),
+                format!("{} points", self.points[player]),
+            )
+        })
+    }
+}
+
+/** A skin a hole to whoever alone has the lowest score; on a tie it
+ * carries over and the next hole is worth one more. **/
+pub struct Skins {
+    skins: Vec<u32>,
+    carry: u32,
+}
+
+impl Skins {
+    pub fn new(golfers: usize) -> Self {
+        Skins {
+            skins: vec![0; golfers],
+            carry: 0,
+        }
+    }
+}
+
+impl ScoringMode for Skins {
+    fn name(&self) -> &'static str {
+        "Skins"
+    }
+
+    fn record(&mut self, scorecard: &Scorecard, number: usize) {
+        let scores: Vec<(usize, u32)> = (0..self.skins.len())
+            .filter_map(|player| scorecard.score(player, number).map(|s| (player, s.strokes)))
+            .collect();
+        let best = scores.iter().map(|(_, strokes)| *strokes).min();
+        let winners: Vec<usize> = scores
+            .iter()
+            .filter(|(_, strokes)| Some(*strokes) == best)
+            .map(|(player, _)| *player)
+            .collect();
+        match winners.as_slice() {
+            [winner] => {
+                self.skins[*winner] += 1 + self.carry;
+                self.carry = 0;
+            }
+            _ => self.carry += 1,
+        }
+    }
+
+    fn results(&self) -> Vec<String> {
+        let mut lines: Vec<String> = (0..self.skins.len())
+            .sorted_by_key(|&player| std::cmp::Reverse(self.skins[player]))
+            .map(|player| format!("P{} {} skins", player + 1, self.skins[player]))
+            .collect();
+        if self.carry > 0 {
+            lines.push(format!("{} skins left unclaimed", self.carry));
+        }
+        lines
+    }
+
+    fn status(&self) -> Option<(String, String)> {
+        let won: u32 = self.skins.iter().sum();
+        Some((
+            format!("{} skins won", won),
+            format!("{} carried", self.carry),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(par: u32, index: u32, strokes: u32) -> HoleScore {
+        HoleScore {
+            player: 0,
+            number: 1,
+            name: String::new(),
+            par,
+            handicap: index,
+            strokes,
+        }
+    }
+
+    // three par 4s to record scores against, from a course file `name` of the
+    // test's own so tests running side by side don't share it
+    fn holes(name: &str) -> Vec<Hole> {
+        let hole = "[[holes]]\nname = \"Hole\"\npar = 4\ngrid = \"\"\"\nF\n.\nT\n\"\"\"\n";
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, format!("name = \"Skins\"\n{}", hole.repeat(3))).unwrap();
+        let mut course = Course::load(path.to_str().unwrap()).unwrap();
+        let mut holes = vec![course.current().unwrap()];
+        while let Some(hole) = course.advance() {
+            holes.push(hole);
+        }
+        holes
+    }
+
+    #[test]
+    fn a_full_round_gives_strokes_on_the_hardest_holes_first() {
+        let indexes: Vec<u32> = (1..=18).collect();
+        let stableford = Stableford::new(1, &[20], Some(&indexes));
+        assert_eq!(stableford.strokes_received(20, 1), 2);
+        assert_eq!(stableford.strokes_received(20, 2), 2);
+        assert_eq!(stableford.strokes_received(20, 3), 1);
+        assert_eq!(stableford.strokes_received(20, 18), 1);
+        assert_eq!(stableford.strokes_received(0, 1), 0);
+    }
+
+    #[test]
+    fn a_nine_hole_round_gives_half_the_strokes() {
+        let indexes: Vec<u32> = (1..=17).step_by(2).collect();
+        let stableford = Stableford::new(1, &[10], Some(&indexes));
+        let given: u32 = indexes
+            .iter()
+            .map(|&index| stableford.strokes_received(10, index))
+            .sum();
+        assert_eq!(given, 5);
+        assert_eq!(stableford.strokes_received(10, 9), 1);
+        assert_eq!(stableford.strokes_received(10, 11), 0);
+    }
+
+    #[test]
+    fn points_are_counted_from_a_net_par() {
+        let indexes: Vec<u32> = (1..=18).collect();
+        let stableford = Stableford::new(1, &[18], Some(&indexes));
+        assert_eq!(stableford.points(&score(4, 1, 4), 0), 2);
+        assert_eq!(stableford.points(&score(4, 1, 3), 0), 3);
+        assert_eq!(stableford.points(&score(4, 1, 8), 0), 0);
+        // a stroke received turns a bogey into a net par
+        assert_eq!(stableford.points(&score(4, 1, 5), 18), 2);
+    }
+
+    #[test]
+    fn a_tied_skin_carries_to_the_next_hole() {
+        let holes = holes("carried_skins_links.toml");
+        let mut card = Scorecard::new();
+        let mut skins = Skins::new(2);
+        for (hole, strokes) in holes.iter().zip([(4, 4), (5, 5), (3, 4)].iter()) {
+            card.record(0, hole, strokes.0);
+            card.record(1, hole, strokes.1);
+            skins.record(&card, hole.number);
+        }
+        assert_eq!(skins.skins, vec![3, 0]);
+        assert_eq!(skins.carry, 0);
+    }
+
+    #[test]
+    fn a_skin_tied_on_the_last_hole_is_left_unclaimed() {
+        let holes = holes("unclaimed_skins_links.toml");
+        let mut card = Scorecard::new();
+        let mut skins = Skins::new(2);
+        card.record(0, &holes[0], 4);
+        card.record(1, &holes[0], 4);
+        skins.record(&card, holes[0].number);
+        assert_eq!(skins.carry, 1);
+        assert_eq!(skins.results().last().unwrap(), "1 skins left unclaimed");
+    }
+}
@@ -7,7 +7,7 @@ pub struct Aim {
 }
 
 impl Aim {
-    const RATE: f32 = 3.;
+    pub const RATE: f32 = 3.;
 
     pub fn new() -> Self {
         Self { degrees: 0. }
@@ -34,128 +34,580 @@ pub enum Swing {
     Accuracy(f32, f32),
 }
 
-impl Swing {}
+impl Swing {
+    // meter points the accuracy cursor moves per frame on its way back
+    pub const RETURN_RATE: f32 = 2.;
+    // past this point the swing releases on its own, as a full miss
+    pub const ACCURACY_FLOOR: f32 = -15.;
+    // meter points the power bar fills per frame
+    pub const POWER_RATE: f32 = 1.;
+    // putts fill slower, for finer control over the stroke
+    pub const PUTT_POWER_RATE: f32 = 0.5;
+    // lateral acceleration (m/s^2) per meter point missed from the sweet spot
+    const SIDESPIN_PER_POINT: f32 = 0.12;
+    // degrees a putt is pulled or pushed per meter point missed
+    const PUTT_PULL_PER_POINT: f32 = 0.3;
+
+    /** Curve imparted by releasing `accuracy` points off the sweet spot at zero.
+     * Early releases hook left (negative), late releases slice right. **/
+    pub fn side_spin(accuracy: &f32) -> f32 {
+        -*accuracy * Swing::SIDESPIN_PER_POINT
+    }
+
+    /** Putts have no spin to curve them; a mistimed stroke starts off line instead. **/
+    pub fn putt_pull(accuracy: &f32) -> f32 {
+        *accuracy * Swing::PUTT_PULL_PER_POINT
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Flight {
+    Carry,
+    Rolling,
+    Stopped,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Cup {
+    Drop,
+    LipOut,
+    Over,
+}
 
 #[derive(Copy, Clone, Debug)]
 pub struct Travel {
     pub direction: f32,
-    initial_velocity: f32,
-    fx: f32,
-    fy: f32,
     velocity_x: f32,
     velocity_y: f32,
+    // lateral, positive to the right of `direction`
+    velocity_z: f32,
     ax: f32,
     ay: f32,
+    az: f32,
     sy: f32,
     lift_mag: f32,
+    side_spin: f32,
     t_elapsed: f32,
+    pub flight: Flight,
+    pub bounces: u32,
+    pub putt: bool,
+    pub lipped_out: bool,
+    // still inside the obstacle it last clipped
+    obstructed: bool,
 }
 
-/** TODO: implement ground travel after carry **/
 impl Travel {
     const GRAVITY: f32 = -9.81;
     const DIMPLING: f32 = 0.2;
-    const RPM: f32 = 3275.;
     const RHO: f32 = 1.225;
     const AREA: f32 = 0.00138;
     const SPIN_FACTOR: f32 = -0.00026;
     const LIFT_FACTOR: f32 = 0.285;
     const MASS: f32 = 0.045;
-    const METERS_PER_TILE: f32 = 8.33333;
+    const METERS_PER_TILE: f32 = Map::METERS_PER_TILE;
+    // below this rebound speed (m/s) the ball stops bouncing and starts to roll
+    const MIN_BOUNCE_VELOCITY: f32 = 1.5;
+    // share of horizontal speed kept through each bounce
+    const BOUNCE_RETENTION: f32 = 0.7;
+    // Stimpmeter release speed (m/s) and the feet its roll-out is measured in
+    const STIMP_RELEASE_VELOCITY: f32 = 1.83;
+    const METERS_PER_FOOT: f32 = 0.3048;
+    const MAX_PUTT_VELOCITY: f32 = 8.;
+    // fastest a ball can cross the cup and still drop
+    const CUP_CAPTURE_SPEED: f32 = 1.3;
+    // a little faster than capture speed, the ball catches the lip and spins out
+    const LIP_OUT_SPEED: f32 = 2.;
+    const LIP_OUT_DEFLECTION: f32 = 60.;
+    // share of speed kept when glancing off an obstacle
+    const DEFLECTION_RETENTION: f32 = 0.4;
+    // share of speed kept when an obstacle knocks the ball down
+    const KNOCKDOWN_RETENTION: f32 = 0.1;
+    // how near (in tiles) the ball's path must pass the centre of the flag tile
+    pub const CUP_RADIUS: f32 = 0.5;
+    // seconds of flight played out each frame, twice real time at 30 fps;
+    // the planner and distance checks step the ball the same way
+    pub const STEP: f32 = 1. / 15.;
 
     fn drag(v: f32) -> f32 {
         -0.5 * Travel::RHO * (v.powf(2.)) * Travel::DIMPLING * Travel::AREA
     }
 
+    /** Drag opposing motion at signed airspeed `v`. **/
+    fn drag_signed(v: f32) -> f32 {
+        Travel::drag(v) * v.signum()
+    }
+
+    /** Deceleration of a ball rolling over `surface`. **/
+    fn rolling_ax(surface: &Surface) -> f32 {
+        match surface.stimp {
+            Some(stimp) => {
+                -Travel::STIMP_RELEASE_VELOCITY.powf(2.) / (2. * stimp * Travel::METERS_PER_FOOT)
+            }
+            None => Travel::GRAVITY * surface.friction,
+        }
+    }
+
     fn meters_to_tile_distance(meters: f32) -> f32 {
         meters / Travel::METERS_PER_TILE
     }
 
-    pub fn new(power: &f32, aim: &Aim, club: &Club) -> Self {
-        let lift_mag: f32 = Travel::LIFT_FACTOR * (1. - (Travel::SPIN_FACTOR * Travel::RPM).exp());
+    pub fn new(power: &f32, accuracy: &f32, aim: &Aim, club: &Club, lie: &Surface) -> Self {
+        if lie.stimp.is_some() {
+            return Travel::putt(power, accuracy, aim, club, lie);
+        }
+        let lift_mag: f32 =
+            Travel::LIFT_FACTOR * (1. - (Travel::SPIN_FACTOR * club.spin_rpm).exp());
         let theta_rad = (club.loft_deg).to_radians();
         let fx = theta_rad.cos();
         let fy = theta_rad.sin();
-        let vi = *power / 100. * club.max_initial_velocity;
+        let vi = *power / 100. * club.max_initial_velocity * lie.lie;
         let vx = vi * fx;
         let vy = vi * fy;
         let ax = Travel::drag(vx) / Travel::MASS;
         let ay = (Travel::drag(vy) / Travel::MASS) + Travel::GRAVITY;
         Travel {
             direction: aim.degrees,
-            initial_velocity: vi,
-            fx,
-            fy,
             velocity_x: vx,
             velocity_y: vy,
+            velocity_z: 0.,
             ax,
             ay,
+            az: 0.,
             sy: 0.,
             lift_mag,
+            side_spin: Swing::side_spin(accuracy),
+            t_elapsed: 0.,
+            flight: if vi > 0. {
+                Flight::Carry
+            } else {
+                Flight::Stopped
+            },
+            bounces: 0,
+            putt: false,
+            lipped_out: false,
+            // the tile the ball is played from never blocks it
+            obstructed: true,
+        }
+    }
+
+    /** A putt never leaves the ground: it starts rolling off the face. **/
+    fn putt(power: &f32, accuracy: &f32, aim: &Aim, club: &Club, green: &Surface) -> Self {
+        let vi = *power / 100. * club.max_initial_velocity.min(Travel::MAX_PUTT_VELOCITY);
+        Travel {
+            direction: aim.degrees + Swing::putt_pull(accuracy),
+            velocity_x: vi,
+            velocity_y: 0.,
+            velocity_z: 0.,
+            ax: Travel::rolling_ax(green),
+            ay: 0.,
+            az: 0.,
+            sy: 0.,
+            lift_mag: 0.,
+            side_spin: 0.,
             t_elapsed: 0.,
+            flight: if vi > 0. {
+                Flight::Rolling
+            } else {
+                Flight::Stopped
+            },
+            bounces: 0,
+            putt: true,
+            lipped_out: false,
+            // the tile the ball is played from never blocks it
+            obstructed: true,
+        }
+    }
+
+    /** Tiles a full, straight swing with `club` covers from `lie`, carry and
+     * roll, over flat ground in still air. **/
+    pub fn full_distance(club: &Club, lie: &Surface) -> f32 {
+        let mut travel = Travel::new(&100., &0., &Aim::new(), club, lie);
+        let ground = Ground {
+            surface: if lie.stimp.is_some() {
+                *lie
+            } else {
+                Surface::FAIRWAY
+            },
+            height: 0.,
+            slope: (0., 0.),
+        };
+        let still = Wind::of(0., 0., 0.);
+        let mut distance = 0.;
+        while !travel.finished() && travel.t_elapsed < 60. {
+            distance += travel.tile_distance(Travel::STEP);
+            travel.tick(Travel::STEP, &ground, &still);
         }
+        distance
     }
 
     pub fn finished(&self) -> bool {
-        self.sy < 0.
+        self.flight == Flight::Stopped
     }
 
     pub fn tile_distance(&self, dt: f32) -> f32 {
-        let meters = self.velocity_x * dt + 0.5 * self.ax * dt.powf(2.);
+        let meters = match self.flight {
+            Flight::Carry => self.velocity_x * dt + 0.5 * self.ax * dt.powf(2.),
+            Flight::Rolling => self.velocity_x * dt,
+            Flight::Stopped => 0.,
+        };
         Travel::meters_to_tile_distance(meters)
     }
 
-    pub fn tick(&mut self, dt: f32) {
+    /** Sideways drift over `dt`, positive to the right of the shot line. **/
+    pub fn lateral_distance(&self, dt: f32) -> f32 {
+        let meters = match self.flight {
+            Flight::Carry => self.velocity_z * dt + 0.5 * self.az * dt.powf(2.),
+            Flight::Rolling => self.velocity_z * dt,
+            Flight::Stopped => 0.,
+        };
+        Travel::meters_to_tile_distance(meters)
+    }
+
+    /** Advance the ball by `dt` seconds over the `ground` it is above. **/
+    pub fn tick(&mut self, dt: f32, ground: &Ground, wind: &Wind) {
+        match self.flight {
+            Flight::Carry => self.carry(dt, ground, wind),
+            Flight::Rolling => self.roll(dt, ground),
+            Flight::Stopped => (),
+        }
+        self.t_elapsed += dt;
+    }
+
+    /** Split the ground's slope into (along the shot line, to its right). **/
+    fn relative_slope(&self, ground: &Ground) -> (f32, f32) {
+        let (gx, gy) = ground.slope;
+        let forward = (self.direction + 90.).to_radians();
+        let right = self.direction.to_radians();
+        (
+            gx * forward.cos() - gy * forward.sin(),
+            gx * right.cos() - gy * right.sin(),
+        )
+    }
+
+    fn carry(&mut self, dt: f32, ground: &Ground, wind: &Wind) {
+        let (tailwind, crosswind) = wind.relative_to(self.direction);
         let sy: f32 = self.sy + self.velocity_y * dt + (0.5 * self.ay * dt.powf(2.));
         let vx = self.velocity_x + self.ax * dt;
         let vy = self.velocity_y + self.ay * dt;
+        let vz = self.velocity_z + self.az * dt;
         let theta_i = (vy / vx).atan();
         let lx = self.lift_mag * theta_i.sin();
         let ly = self.lift_mag * theta_i.cos();
-        let ax = Travel::drag(vx) / Travel::MASS + (lx / Travel::MASS);
+        let ax = Travel::drag_signed(vx - tailwind) / Travel::MASS + (lx / Travel::MASS);
         let ay = Travel::drag(vy) / Travel::MASS + Travel::GRAVITY + (ly / Travel::MASS);
+        let az = Travel::drag_signed(vz - crosswind) / Travel::MASS + self.side_spin;
         self.sy = sy;
-        println!("Ball at height: {:?}", sy);
         self.velocity_x = vx;
         self.velocity_y = vy;
+        self.velocity_z = vz;
         self.ax = ax;
         self.ay = ay;
-        self.t_elapsed += dt;
+        self.az = az;
+        if self.sy < ground.height {
+            self.land(ground);
+        }
     }
-}
 
-#[derive(Copy, Clone, Debug)]
-struct Finished {}
+    fn land(&mut self, ground: &Ground) {
+        let impact = -self.velocity_y;
+        let rebound = impact * ground.surface.restitution;
+        self.sy = ground.height;
+        // a downslope kicks the ball on, an upslope checks it
+        let (along, _) = self.relative_slope(ground);
+        self.velocity_x -= along * impact * Travel::BOUNCE_RETENTION;
+        if rebound > Travel::MIN_BOUNCE_VELOCITY {
+            self.bounces += 1;
+            self.velocity_x *= Travel::BOUNCE_RETENTION;
+            self.velocity_z *= Travel::BOUNCE_RETENTION;
+            self.velocity_y = rebound;
+            // the landing scrubs off the spin that was generating lift and curve
+            self.lift_mag = 0.;
+            self.side_spin = 0.;
+            self.ax = Travel::drag(self.velocity_x) / Travel::MASS;
+            self.ay = Travel::drag(self.velocity_y) / Travel::MASS + Travel::GRAVITY;
+        } else {
+            self.velocity_y = 0.;
+            self.velocity_z = 0.;
+            self.ay = 0.;
+            self.az = 0.;
+            self.ax = Travel::rolling_ax(&ground.surface);
+            self.flight = if self.velocity_x > 0. {
+                Flight::Rolling
+            } else {
+                Flight::Stopped
+            };
+        }
+    }
+
+    pub fn on_ground(&self) -> bool {
+        self.flight != Flight::Carry
+    }
+
+    /** Horizontal speed, m/s. **/
+    pub fn speed(&self) -> f32 {
+        (self.velocity_x.powf(2.) + self.velocity_z.powf(2.)).sqrt()
+    }
+
+    /** The ball's path crosses the cup: it drops, lips out, or runs over it. **/
+    pub fn reach_cup(&mut self) -> Cup {
+        if !self.on_ground() || self.lipped_out {
+            return Cup::Over;
+        }
+        let speed = self.speed();
+        if speed <= Travel::CUP_CAPTURE_SPEED {
+            self.drop_in();
+            Cup::Drop
+        } else if speed <= Travel::LIP_OUT_SPEED {
+            self.lip_out();
+            Cup::LipOut
+        } else {
+            Cup::Over
+        }
+    }
+
+    /** Check the ball against an obstacle of `height` on the tile below it;
+     * true when it clips the obstacle. **/
+    pub fn pass_over(&mut self, height: Option<f32>, ground: &Ground) -> bool {
+        match height {
+            Some(height) if self.sy - ground.height < height && self.flight != Flight::Stopped => {
+                let clipped = !self.obstructed;
+                if clipped {
+                    self.obstructed = true;
+                    self.hit_obstacle();
+                }
+                clipped
+            }
+            _ => {
+                self.obstructed = false;
+                false
+            }
+        }
+    }
+
+    fn hit_obstacle(&mut self) {
+        let mut rng = RandomNumberGenerator::new();
+        self.lift_mag = 0.;
+        self.side_spin = 0.;
+        self.velocity_z = 0.;
+        self.az = 0.;
+        if rng.range(0, 2) == 0 {
+            self.velocity_x *= Travel::KNOCKDOWN_RETENTION;
+            self.velocity_y = self.velocity_y.min(0.);
+        } else {
+            let side = if rng.range(0, 2) == 0 { -1. } else { 1. };
+            let angle: f32 = rng.range(30., 120.);
+            self.direction += side * angle;
+            self.velocity_x *= Travel::DEFLECTION_RETENTION;
+        }
+        self.ax = Travel::drag(self.velocity_x) / Travel::MASS;
+    }
+
+    fn lip_out(&mut self) {
+        let mut rng = RandomNumberGenerator::new();
+        let side = if rng.range(0, 2) == 0 { -1. } else { 1. };
+        self.direction += side * Travel::LIP_OUT_DEFLECTION;
+        self.velocity_x *= 0.5;
+        self.lipped_out = true;
+    }
+
+    /** The ball is picked up, as after leaving the course. **/
+    pub fn stop(&mut self) {
+        self.drop_in();
+    }
+
+    fn drop_in(&mut self) {
+        self.velocity_x = 0.;
+        self.velocity_z = 0.;
+        self.ax = 0.;
+        self.az = 0.;
+        self.flight = Flight::Stopped;
+    }
+
+    fn roll(&mut self, dt: f32, ground: &Ground) {
+        // gravity pulls the ball down the slope, friction slows it along its path
+        let (along, cross) = self.relative_slope(ground);
+        let friction = -Travel::rolling_ax(&ground.surface);
+        self.ax = Travel::GRAVITY * along;
+        self.az = Travel::GRAVITY * cross;
+        let vx = self.velocity_x + self.ax * dt;
+        let vz = self.velocity_z + self.az * dt;
+        let speed = (vx.powf(2.) + vz.powf(2.)).sqrt();
+        let slowed = (speed - friction * dt).max(0.);
+        let scale = if speed > 0. { slowed / speed } else { 0. };
+        self.velocity_x = vx * scale;
+        self.velocity_z = vz * scale;
+        self.sy = ground.height;
+        let pull = (self.ax.powf(2.) + self.az.powf(2.)).sqrt();
+        if slowed <= 0. && pull <= friction {
+            self.flight = Flight::Stopped;
+        }
+    }
+}
 
 #[derive(Copy, Clone, Debug)]
 pub enum TurnStage {
-    ClubSelection(ClubSet, usize),
-    Aiming(Aim, Club),
-    Swinging(Swing, Aim, Club),
+    ClubSelection(usize),
+    Aiming(Aim, usize),
+    Swinging(Swing, Aim, usize),
     Traveling(Travel),
     Finished,
 }
 
 impl TurnStage {
     pub fn start() -> TurnStage {
-        let set = ClubSet::default();
-        TurnStage::ClubSelection(set, 0)
+        TurnStage::ClubSelection(0)
     }
 
-    fn start_swing(aim: Aim, club: Club) -> TurnStage {
+    fn start_swing(aim: Aim, club: usize) -> TurnStage {
         TurnStage::Swinging(Swing::Start, aim, club)
     }
 
-    pub fn next(&self) -> TurnStage {
+    /** Advance to the following stage; `lie` is the surface the ball is played from. **/
+    pub fn next(&self, clubs: &ClubSet, lie: &Surface) -> TurnStage {
         match self {
-            TurnStage::ClubSelection(clubs, club) => TurnStage::Aiming(Aim::new(), clubs.at(club)),
-            TurnStage::Aiming(aim, club) => TurnStage::start_swing(aim.clone(), club.clone()),
+            TurnStage::ClubSelection(club) => TurnStage::Aiming(Aim::new(), *club),
+            TurnStage::Aiming(aim, club) => TurnStage::start_swing(*aim, *club),
             TurnStage::Swinging(swing, aim, club) => match swing {
-                Swing::Accuracy(pow, _acc) => TurnStage::Traveling(Travel::new(pow, aim, club)),
-                _accuracy => TurnStage::Swinging(swing.clone(), aim.clone(), club.clone()),
+                Swing::Accuracy(pow, acc) => {
+                    TurnStage::Traveling(Travel::new(pow, acc, aim, clubs.at(club), lie))
+                }
+                _accuracy => TurnStage::Swinging(*swing, *aim, *club),
             },
             TurnStage::Traveling(_) => TurnStage::Finished,
-            TurnStage::Finished => TurnStage::start(),
+            TurnStage::Finished => TurnStage::ClubSelection(clubs.default_for(lie)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(surface: Surface) -> Ground {
+        Ground {
+            surface,
+            height: 0.,
+            slope: (0., 0.),
         }
     }
+
+    // tiles covered in still air until the ball stops
+    fn play_out(mut travel: Travel, ground: &Ground) -> (f32, Travel) {
+        let still = Wind::of(0., 0., 0.);
+        let mut distance = 0.;
+        while !travel.finished() && travel.t_elapsed < 60. {
+            distance += travel.tile_distance(Travel::STEP);
+            travel.tick(Travel::STEP, ground, &still);
+        }
+        (distance, travel)
+    }
+
+    fn bag() -> ClubSet {
+        ClubSet::load(ClubSet::DEFAULT_FILE).expect("clubs.txt should load")
+    }
+
+    fn club(clubs: &ClubSet, short_name: &str) -> Club {
+        clubs
+            .iter()
+            .find(|c| c.short_name == short_name)
+            .expect("club missing from the bag")
+            .clone()
+    }
+
+    #[test]
+    fn longer_clubs_go_farther() {
+        let clubs = bag();
+        let driver = Travel::full_distance(&club(&clubs, "Dr"), &Surface::FAIRWAY);
+        let seven = Travel::full_distance(&club(&clubs, "7i"), &Surface::FAIRWAY);
+        let wedge = Travel::full_distance(&club(&clubs, "SW"), &Surface::FAIRWAY);
+        assert!(driver > seven, "{} <= {}", driver, seven);
+        assert!(seven > wedge, "{} <= {}", seven, wedge);
+        assert!(wedge > 0.);
+    }
+
+    #[test]
+    fn a_full_shot_bounces_then_rolls_out() {
+        let driver = club(&bag(), "Dr");
+        let travel = Travel::new(&100., &0., &Aim::new(), &driver, &Surface::FAIRWAY);
+        let (distance, travel) = play_out(travel, &flat(Surface::FAIRWAY));
+        assert!(travel.bounces > 0);
+        assert!(travel.finished());
+        assert!(distance > 0.);
+    }
+
+    #[test]
+    fn sand_stops_the_ball_sooner_than_fairway() {
+        let driver = club(&bag(), "Dr");
+        let shot = Travel::new(&100., &0., &Aim::new(), &driver, &Surface::FAIRWAY);
+        let (fairway, _) = play_out(shot, &flat(Surface::FAIRWAY));
+        let (sand, _) = play_out(shot, &flat(Surface::BUNKER));
+        assert!(fairway > sand, "{} <= {}", fairway, sand);
+    }
+
+    #[test]
+    fn a_low_ball_hits_a_tree_and_a_high_one_clears_it() {
+        let driver = club(&bag(), "Dr");
+        let grass = flat(Surface::FAIRWAY);
+        let tree = MapTile::Tree.obstacle_height();
+        let mut low = Travel::new(&100., &0., &Aim::new(), &driver, &Surface::FAIRWAY);
+        // off the tile it was struck from
+        assert!(!low.pass_over(None, &grass));
+        let mut high = low;
+        high.sy = tree.unwrap() + 1.;
+        assert!(low.pass_over(tree, &grass));
+        assert!(!high.pass_over(tree, &grass));
+    }
+
+    #[test]
+    fn a_slow_putt_drops_and_a_firmer_one_lips_out() {
+        let putter = club(&bag(), "Pt");
+        let at_speed = |speed: f32| {
+            let full = putter.max_initial_velocity.min(Travel::MAX_PUTT_VELOCITY);
+            let mut travel = Travel::new(
+                &(speed / full * 100.),
+                &0.,
+                &Aim::new(),
+                &putter,
+                &Surface::GREEN,
+            );
+            travel.reach_cup()
+        };
+        assert_eq!(at_speed(Travel::CUP_CAPTURE_SPEED - 0.2), Cup::Drop);
+        assert_eq!(at_speed(Travel::CUP_CAPTURE_SPEED + 0.2), Cup::LipOut);
+        assert_eq!(at_speed(Travel::LIP_OUT_SPEED + 1.), Cup::Over);
+    }
+
+    #[test]
+    fn a_lipped_out_ball_cannot_drop_on_the_same_stroke() {
+        let putter = club(&bag(), "Pt");
+        let full = putter.max_initial_velocity.min(Travel::MAX_PUTT_VELOCITY);
+        let mut travel = Travel::new(
+            &((Travel::CUP_CAPTURE_SPEED + 0.2) / full * 100.),
+            &0.,
+            &Aim::new(),
+            &putter,
+            &Surface::GREEN,
+        );
+        assert_eq!(travel.reach_cup(), Cup::LipOut);
+        assert_eq!(travel.reach_cup(), Cup::Over);
+    }
+
+    #[test]
+    fn putts_roll_farther_on_faster_greens() {
+        let putter = club(&bag(), "Pt");
+        let slow = Surface {
+            stimp: Some(7.),
+            ..Surface::GREEN
+        };
+        let fast = Surface {
+            stimp: Some(13.),
+            ..Surface::GREEN
+        };
+        let putt = |green: &Surface| {
+            let travel = Travel::new(&40., &0., &Aim::new(), &putter, green);
+            assert!(travel.putt);
+            play_out(travel, &flat(*green)).0
+        };
+        assert!(putt(&fast) > putt(&slow));
+    }
 }
@@ -1,25 +1,123 @@
 use crate::prelude::*;
 
-#[derive(Debug, Clone, Copy)]
+/** How a tile plays: landing, rolling, and hitting out of it. **/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Surface {
+    /// rolling resistance, as a fraction of gravity
+    pub friction: f32,
+    /// share of vertical speed returned when the ball lands
+    pub restitution: f32,
+    /// scales the club's max initial velocity for a shot played from here
+    pub lie: f32,
+    /// green speed in feet on the Stimpmeter, set where the ball is putted
+    pub stimp: Option<f32>,
+}
+
+impl Surface {
+    pub const TEE_BOX: Surface = Surface {
+        friction: 0.12,
+        restitution: 0.4,
+        lie: 1.,
+        stimp: None,
+    };
+    pub const FAIRWAY: Surface = Surface {
+        friction: 0.12,
+        restitution: 0.4,
+        lie: 1.,
+        stimp: None,
+    };
+    pub const GREEN: Surface = Surface {
+        friction: 0.08,
+        restitution: 0.3,
+        lie: 1.,
+        stimp: Some(10.),
+    };
+    pub const FIRST_CUT: Surface = Surface {
+        friction: 0.2,
+        restitution: 0.3,
+        lie: 0.95,
+        stimp: None,
+    };
+    pub const ROUGH: Surface = Surface {
+        friction: 0.3,
+        restitution: 0.2,
+        lie: 0.85,
+        stimp: None,
+    };
+    pub const DEEP_ROUGH: Surface = Surface {
+        friction: 0.6,
+        restitution: 0.1,
+        lie: 0.6,
+        stimp: None,
+    };
+    pub const BUNKER: Surface = Surface {
+        friction: 3.,
+        restitution: 0.05,
+        lie: 0.5,
+        stimp: None,
+    };
+    pub const WATER: Surface = Surface {
+        friction: 1.,
+        restitution: 0.,
+        lie: 1.,
+        stimp: None,
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MapTile {
     Tee,
     TeeBox,
     Fairway,
     Green,
     Flag,
+    FirstCut,
     Rough,
     DeepRough,
+    Bunker,
+    Water,
+    OutOfBounds,
+    Tree,
+    Bush,
 }
 
 impl MapTile {
-    pub fn from_char(c: &char) -> MapTile {
-        match c {
+    pub fn from_char(c: &char) -> Option<MapTile> {
+        let tile = match c {
             'T' => MapTile::Tee,
             'D' => MapTile::TeeBox,
             '=' => MapTile::Fairway,
             '@' => MapTile::Green,
             'F' => MapTile::Flag,
-            _ => MapTile::DeepRough,
+            'S' => MapTile::Bunker,
+            '~' => MapTile::Water,
+            'X' => MapTile::OutOfBounds,
+            'Y' => MapTile::Tree,
+            'B' => MapTile::Bush,
+            ':' => MapTile::FirstCut,
+            ',' => MapTile::Rough,
+            '.' => MapTile::DeepRough,
+            _ => return None,
+        };
+        Some(tile)
+    }
+
+    /** The character `from_char` reads back as this tile. **/
+    pub fn to_char(self) -> char {
+        match self {
+            MapTile::Tee => 'T',
+            MapTile::TeeBox => 'D',
+            MapTile::Fairway => '=',
+            MapTile::Green => '@',
+            MapTile::Flag => 'F',
+            MapTile::FirstCut => ':',
+            MapTile::Rough => ',',
+            MapTile::DeepRough => '.',
+            MapTile::Bunker => 'S',
+            MapTile::Water => '~',
+            MapTile::OutOfBounds => 'X',
+            MapTile::Tree => 'Y',
+            MapTile::Bush => 'B',
         }
     }
 
@@ -30,8 +128,14 @@ impl MapTile {
             MapTile::Fairway => '█',
             MapTile::Green => '█',
             MapTile::Flag => 'F',
+            MapTile::FirstCut => '░',
             MapTile::Rough => '░',
             MapTile::DeepRough => '░',
+            MapTile::Bunker => '▒',
+            MapTile::Water => '≈',
+            MapTile::OutOfBounds => '#',
+            MapTile::Tree => '♣',
+            MapTile::Bush => '♠',
         };
         to_cp437(c)
     }
@@ -47,18 +151,89 @@ impl MapTile {
             MapTile::Fairway => FOREST_GREEN,
             MapTile::Green => GREEN,
             MapTile::Flag => RED,
+            MapTile::FirstCut => FOREST_GREEN,
             MapTile::Rough => DARKGREEN,
             MapTile::DeepRough => DARKOLIVEGREEN,
+            MapTile::Bunker => BURLYWOOD,
+            MapTile::Water => DODGERBLUE,
+            MapTile::OutOfBounds => DIMGRAY,
+            MapTile::Tree => FOREST_GREEN,
+            MapTile::Bush => OLIVE,
         }
     }
 
+    pub fn surface(self) -> Surface {
+        match self {
+            MapTile::Tee | MapTile::TeeBox => Surface::TEE_BOX,
+            MapTile::Fairway => Surface::FAIRWAY,
+            MapTile::Green | MapTile::Flag => Surface::GREEN,
+            MapTile::FirstCut => Surface::FIRST_CUT,
+            MapTile::Rough => Surface::ROUGH,
+            MapTile::DeepRough | MapTile::OutOfBounds => Surface::DEEP_ROUGH,
+            MapTile::Tree | MapTile::Bush => Surface::DEEP_ROUGH,
+            MapTile::Bunker => Surface::BUNKER,
+            MapTile::Water => Surface::WATER,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MapTile::Tee | MapTile::TeeBox => "Tee Box",
+            MapTile::Fairway => "Fairway",
+            MapTile::Green | MapTile::Flag => "Green",
+            MapTile::FirstCut => "First Cut",
+            MapTile::Rough => "Rough",
+            MapTile::DeepRough => "Deep Rough",
+            MapTile::Bunker => "Bunker",
+            MapTile::Water => "Water",
+            MapTile::OutOfBounds => "Out of Bounds",
+            MapTile::Tree => "Trees",
+            MapTile::Bush => "Bushes",
+        }
+    }
+
+    /** Height in meters of anything standing on this tile that the ball has to clear. **/
+    pub fn obstacle_height(self) -> Option<f32> {
+        match self {
+            MapTile::Tree => Some(15.),
+            MapTile::Bush => Some(3.),
+            _ => None,
+        }
+    }
+
+    /** A ball coming to ground here is played under penalty. **/
+    pub fn is_hazard(self) -> bool {
+        matches!(self, MapTile::Water | MapTile::OutOfBounds)
+    }
+
     pub fn bg(self) -> (u8, u8, u8) {
         match self {
             MapTile::Tee => DARKGREEN,
             MapTile::Flag => LIGHTGREEN,
+            MapTile::FirstCut => BLACK,
             MapTile::Rough => BLACK,
             MapTile::DeepRough => BLACK,
+            MapTile::Bunker => WHEAT,
+            MapTile::Water => NAVY,
+            MapTile::OutOfBounds => BLACK,
+            MapTile::Tree => BLACK,
+            MapTile::Bush => BLACK,
             _ => self.color(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_tile_reads_back_from_its_char() {
+        for c in "TD=@FS~XYB:,.".chars() {
+            let tile = MapTile::from_char(&c).expect("a map character");
+            assert_eq!(tile.to_char(), c);
+            assert_eq!(MapTile::from_char(&tile.to_char()), Some(tile));
+        }
+        assert_eq!(MapTile::from_char(&'?'), None);
+    }
+}
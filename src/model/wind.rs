@@ -0,0 +1,56 @@
+use crate::prelude::*;
+
+#[derive(Copy, Clone, Debug)]
+pub struct Wind {
+    // steady wind speed, meters per second
+    pub speed: f32,
+    // degrees the wind blows toward, same convention as `Aim`
+    pub direction: f32,
+    // peak extra speed added by gusts
+    pub gust: f32,
+    t_elapsed: f32,
+}
+
+impl Wind {
+    const MAX_SPEED: f32 = 8.;
+    const GUST_PERIOD: f32 = 6.;
+    const COMPASS: [&'static str; 8] = ["N", "NW", "W", "SW", "S", "SE", "E", "NE"];
+
+    pub fn of(speed: f32, direction: f32, gust: f32) -> Self {
+        Self {
+            speed,
+            direction,
+            gust,
+            t_elapsed: 0.,
+        }
+    }
+
+    pub fn random() -> Self {
+        let mut rng = RandomNumberGenerator::new();
+        let speed = rng.range(0., Wind::MAX_SPEED);
+        let direction = rng.range(0, 8) as f32 * 45.;
+        let gust = rng.range(0., speed / 2. + f32::EPSILON);
+        Wind::of(speed, direction, gust)
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        self.t_elapsed += dt;
+    }
+
+    pub fn current_speed(&self) -> f32 {
+        let phase = self.t_elapsed / Wind::GUST_PERIOD * std::f32::consts::PI * 2.;
+        self.speed + self.gust * (0.5 + 0.5 * phase.sin())
+    }
+
+    /** Split the wind into (tailwind, crosswind to the right) for a shot toward `degrees`. **/
+    pub fn relative_to(&self, degrees: f32) -> (f32, f32) {
+        let angle = (self.direction - degrees).to_radians();
+        let speed = self.current_speed();
+        (speed * angle.cos(), -speed * angle.sin())
+    }
+
+    pub fn compass(&self) -> &'static str {
+        let octant = (self.direction.rem_euclid(360.) / 45.).round() as usize % 8;
+        Wind::COMPASS[octant]
+    }
+}
@@ -0,0 +1,40 @@
+use crate::prelude::*;
+
+/** Plays the turn when the computer has it: the key it presses stands in
+ * for the keyboard's this frame, so the rest of the turn runs as usual. **/
+pub fn computer_player(
+    dt: Res<FrameTime>,
+    mut key: ResMut<Option<VirtualKeyCode>>,
+    turn_stage: Res<TurnStage>,
+    hole_state: Res<HoleState>,
+    hole: Res<Hole>,
+    wind: Res<Wind>,
+    mut golfers: Query<(&Ball, &Player, &mut Computer), With<Active>>,
+    others: Query<&Player, Without<Active>>,
+) {
+    // the balls go to the tee, and the honor is settled, at the tee-off
+    if let HoleState::TeeOff = *hole_state {
+        return;
+    }
+    // a person on the other side may give the computer its putt
+    if *key == Some(VirtualKeyCode::C) {
+        return;
+    }
+    if let Some((ball, player, mut computer)) = golfers.iter_mut().next() {
+        let pressed = computer.press(
+            dt.seconds(),
+            &turn_stage,
+            ball,
+            &hole.map,
+            &player.clubs,
+            &wind,
+        );
+        // with everyone in, the scorecard stays up until a person moves on
+        let everyone_in = matches!(*turn_stage, TurnStage::Finished)
+            && matches!(*hole_state, HoleState::Holed(_, _))
+            && others.iter().all(|other| other.holed());
+        if !everyone_in {
+            *key = pressed;
+        }
+    }
+}
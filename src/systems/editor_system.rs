@@ -0,0 +1,117 @@
+use crate::prelude::*;
+
+pub fn editor(
+    dt: Res<FrameTime>,
+    key: Res<Option<VirtualKeyCode>>,
+    mut state: ResMut<State<AppState>>,
+    mut editor: ResMut<Editor>,
+    mut hole: ResMut<Hole>,
+    mut course: ResMut<Course>,
+    mut camera: ResMut<Camera>,
+    mut balls: Query<&mut Ball>,
+    window: Res<Window>,
+    clubs: Res<ClubSet>,
+    mut notice: ResMut<Notice>,
+) {
+    notice.tick(dt.seconds());
+    if editor.hole != hole.number {
+        *editor = Editor::new(&hole);
+    }
+    let cursor = editor.cursor;
+    match *key {
+        Some(VirtualKeyCode::Up) => editor.move_cursor(Point::new(0, -1), &hole.map),
+        Some(VirtualKeyCode::Down) => editor.move_cursor(Point::new(0, 1), &hole.map),
+        Some(VirtualKeyCode::Left) => editor.move_cursor(Point::new(-1, 0), &hole.map),
+        Some(VirtualKeyCode::Right) => editor.move_cursor(Point::new(1, 0), &hole.map),
+        Some(VirtualKeyCode::Tab) => editor.next_brush(),
+        Some(VirtualKeyCode::Space) => hole.map.paint(&cursor, editor.brush()),
+        Some(VirtualKeyCode::T) => {
+            if let Some(name) = hole.move_tee(&cursor) {
+                notice.show(&format!(
+                    "{} tee now at ({}, {}); set it in the course file",
+                    name, cursor.x, cursor.y
+                ))
+            }
+        }
+        Some(VirtualKeyCode::F) => {
+            if let Some(name) = hole.move_flag(&cursor) {
+                notice.show(&format!(
+                    "{} pin now at ({}, {}); set it in the course file",
+                    name, cursor.x, cursor.y
+                ))
+            }
+        }
+        Some(VirtualKeyCode::S) => match editor.file.as_ref().map(|f| (f, hole.map.save(f))) {
+            Some((file, Ok(()))) if hole.map.flag_reachable(&clubs) => {
+                notice.show(&format!("Saved {}", file))
+            }
+            Some((_, Ok(()))) => notice.show("Saved, but the flag can't be reached!"),
+            Some((_, Err(error))) => notice.show(&format!("Could not save: {}", error)),
+            None => notice.show("Can't save: the grid is in the course file or generated"),
+        },
+        Some(VirtualKeyCode::Escape) => {
+            // back to the menu, with the ball on the tee as it now stands
+            course.update(&hole);
+            balls.iter_mut().for_each(|mut b| {
+                b.move_to(&hole.map.tee);
+                camera.update(b.tile_position());
+            });
+            if state.set_next(AppState::Menu).is_err() {
+                panic!("Could not leave the editor!")
+            }
+            return;
+        }
+        _ => {}
+    }
+    camera.update(editor.cursor);
+
+    let mut batch = DrawBatch::new();
+    let map = &hole.map;
+    let brush = editor.brush();
+    batch.set(
+        camera.render_coordinate(&editor.cursor),
+        ColorPair::new(YELLOW, map.bg(&editor.cursor)),
+        to_cp437('X'),
+    );
+    let panel_x = camera.width() + 1;
+    let ui_w = window.width as i32 - 1;
+    batch
+        .draw_box(
+            Rect::with_exact(0, camera.height() - 1, ui_w, window.height as i32 - 1),
+            ColorPair::new(WHITE, BLACK),
+        )
+        .draw_box(
+            Rect::with_exact(camera.width(), 0, ui_w, camera.height() - 1),
+            ColorPair::new(WHITE, BLACK),
+        )
+        .print(Point::new(panel_x, 2), "EDITOR")
+        .print(
+            Point::new(panel_x, 4),
+            format!("({}, {})", editor.cursor.x, editor.cursor.y),
+        )
+        .print(Point::new(panel_x, 5), map.tile_at(&editor.cursor).name())
+        .print(Point::new(panel_x, 7), "Brush:")
+        .set(Point::new(panel_x, 8), brush.color_pair(), brush.glyph())
+        .print(Point::new(panel_x + 2, 8), brush.name());
+    let help = [
+        "Arrows move, Tab changes brush, Space paints",
+        "T places the tee, F the flag",
+        "S saves, Esc returns to the menu",
+    ];
+    for (i, line) in help.iter().enumerate() {
+        batch.print(Point::new(2, window.height as i32 - 8 + i as i32), line);
+    }
+    let saving = match &editor.file {
+        Some(file) => format!("Saving to {}", file),
+        None => "No map file to save to; edits last until the game closes".to_string(),
+    };
+    batch.print(Point::new(2, window.height as i32 - 3), saving);
+    if let Some(message) = notice.message() {
+        batch.print_color(
+            Point::new(2, window.height as i32 - 5),
+            message,
+            ColorPair::new(YELLOW, BLACK),
+        );
+    }
+    batch.submit(20220).expect("Editor error");
+}
@@ -1,20 +1,38 @@
 use crate::prelude::*;
 
-pub fn hole_handler(hole: Res<Hole>, balls: Query<&Ball>, hole_state: Res<HoleState>) -> HoleState {
-    match *hole_state {
-        HoleState::TeeOff => HoleState::start_hole(),
-        HoleState::Stroke(strokes) => {
-            if let Some(_) = balls.iter().find(|b| {
-                let ball_at = b.tile_position();
-                hole.map.flag == ball_at
-            }) {
-                HoleState::Holed
-            } else {
-                HoleState::Stroke(strokes)
+pub fn hole_handler(
+    mut hole_state: ResMut<HoleState>,
+    mut hole: ResMut<Hole>,
+    setup: Res<Setup>,
+    mut players: Query<(bevy::Entity, &mut Ball, &mut Player, Option<&Active>)>,
+    mut camera: ResMut<Camera>,
+    commands: &mut Commands,
+) -> HoleState {
+    if let HoleState::TeeOff = *hole_state {
+        // the tees may have been picked since the hole was set out
+        hole.place(&setup);
+        let honor = players.iter_mut().map(|(_, _, p, _)| p.honor).min();
+        for (entity, mut ball, mut player, active) in players.iter_mut() {
+            ball.move_to(&hole.map.tee);
+            player.hole_state = HoleState::start_hole();
+            // whoever has the honor drives first
+            let up = Some(player.honor) == honor;
+            if up {
+                camera.update(ball.tile_position());
+            }
+            match (up, active.is_some()) {
+                (true, false) => {
+                    commands.insert_one(entity, Active);
+                }
+                (false, true) => {
+                    commands.remove_one::<Active>(entity);
+                }
+                _ => {}
             }
         }
-        HoleState::Holed => HoleState::Holed,
+        *hole_state = HoleState::start_hole();
     }
+    *hole_state
 }
 
 pub fn hole_transition(
@@ -22,31 +40,91 @@ pub fn hole_transition(
     commands: &mut Commands,
     mut course: ResMut<Course>,
     mut balls: Query<&mut Ball>,
+    mut players: Query<(&mut Player, Option<&Active>)>,
     window: Res<Window>,
+    turn_stage: Res<TurnStage>,
+    key: Res<Option<VirtualKeyCode>>,
+    setup: Res<Setup>,
+    scorecard: Res<Scorecard>,
+    mut scoring: ResMut<Scoring>,
+    hole: Res<Hole>,
+    mut state: ResMut<State<AppState>>,
 ) {
-    let next_state = match &hole_state {
-        HoleState::Holed => {
-            if let Some(next_hole) = course.next() {
-                let next_map = &next_hole.map;
-                balls.iter_mut().for_each(|mut ball| {
-                    ball.move_to(&next_map.tee);
-                    let cam = Camera::new(
-                        ball.tile_position(),
-                        next_map.width as i32,
-                        next_map.height as i32,
-                        window.width as i32 - 15,
-                        window.height as i32 - 10,
-                    );
-                    commands.insert_resource(cam);
-                });
-                commands.insert_resource(next_hole);
-                HoleState::TeeOff
+    // stay on the holed-out green until the player moves on, and until
+    // everyone else has holed out too
+    let everyone_in = players
+        .iter_mut()
+        .all(|(player, active)| active.is_some() || player.holed());
+    let moving_on = everyone_in
+        && matches!(*turn_stage, TurnStage::Finished)
+        && *key == Some(VirtualKeyCode::Space);
+    if *key == Some(VirtualKeyCode::R) {
+        // play the hole again from the tee; a new score replaces the old one
+        if let Some(hole) = course.current() {
+            set_out(commands, hole, &setup, &mut balls, &window);
+        }
+    } else if let (HoleState::Holed(_, _), true) = (hole_state, moving_on) {
+        award_honors(&mut players, hole_state);
+        scoring.0.record(&scorecard, hole.number);
+        // a match can be over before the last hole
+        let next_hole = if scoring.0.decided() {
+            None
+        } else {
+            course.advance()
+        };
+        if let Some(next_hole) = next_hole {
+            set_out(commands, next_hole, &setup, &mut balls, &window);
+        } else if state.set_next(AppState::RoundComplete).is_err() {
+            panic!("Could not finish the round!")
+        }
+    }
+}
+
+/** Lowest score on the hole just played has the honor on the next tee; a tie
+ * keeps the order from before. **/
+fn award_honors(players: &mut Query<(&mut Player, Option<&Active>)>, active_state: HoleState) {
+    let mut order: Vec<(u32, usize, usize)> = players
+        .iter_mut()
+        .map(|(player, active)| {
+            let state = if active.is_some() {
+                active_state
             } else {
-                // TODO: score card
-                panic!("Course finished!")
-            }
+                player.hole_state
+            };
+            (state.strokes(), player.honor, player.number)
+        })
+        .collect();
+    order.sort_unstable();
+    for (mut player, _) in players.iter_mut() {
+        if let Some(honor) = order.iter().position(|(_, _, n)| *n == player.number) {
+            player.honor = honor;
         }
-        state => *state,
-    };
-    commands.insert_resource(next_state);
+    }
+}
+
+/** Put `hole` in play with the ball on its tee. **/
+pub fn set_out(
+    commands: &mut Commands,
+    mut hole: Hole,
+    setup: &Setup,
+    balls: &mut Query<&mut Ball>,
+    window: &Window,
+) {
+    hole.place(setup);
+    let map = &hole.map;
+    balls.iter_mut().for_each(|mut ball| {
+        ball.move_to(&map.tee);
+        let cam = Camera::new(
+            ball.tile_position(),
+            map.width as i32,
+            map.height as i32,
+            window.width as i32 - 15,
+            window.height as i32 - 10,
+        );
+        commands.insert_resource(cam);
+    });
+    commands.insert_resource(hole);
+    commands.insert_resource(Wind::random());
+    commands.insert_resource(TurnStage::start());
+    commands.insert_resource(HoleState::new());
 }
@@ -1,15 +1,29 @@
 use crate::prelude::*;
 
+const SHADE_DEPTH: f32 = 0.25;
+
 pub fn map_render(hole: Res<Hole>, camera: Res<Camera>) {
     let mut draw = DrawBatch::new();
     let map = &hole.map;
     draw.target(0);
     for point in &camera.map_coords {
         if map.in_bounds(point) {
-            let t = map.tile_at(point);
+            let t = map.shown_at(point);
             let pix = camera.render_coordinate(point);
-            draw.set(pix, t.color_pair(), t.glyph());
+            let shade = map.shade(point);
+            let color = ColorPair::new(shaded(t.color(), shade), shaded(t.bg(), shade));
+            draw.set(pix, color, t.glyph());
         }
     }
     draw.submit(2020).expect("Batch error");
 }
+
+/** Lighten high ground and darken low ground so slopes can be read. **/
+fn shaded(color: (u8, u8, u8), shade: f32) -> RGB {
+    let base = RGB::named(color);
+    if shade >= 0. {
+        base.lerp(RGB::named(WHITE), shade * SHADE_DEPTH)
+    } else {
+        base.lerp(RGB::named(BLACK), -shade * SHADE_DEPTH)
+    }
+}
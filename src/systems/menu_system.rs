@@ -1,19 +1,156 @@
 use crate::prelude::*;
 
-pub fn menu(key: Res<Option<VirtualKeyCode>>, mut state: ResMut<State<AppState>>) {
-    DrawBatch::new()
-        .draw_box(Rect::with_size(20, 20, 40, 6), ColorPair::new(WHITE, BLACK))
-        .print(Point::new(30, 22), "Menu! D to play!")
-        .submit(15050)
-        .expect("Box error");
-    match *key {
-        Some(VirtualKeyCode::D) => {
-            if let Ok(_) = state.set_next(AppState::Playing) {
-                ()
-            } else {
-                panic!("Could not start game!")
+pub fn menu(
+    key: Res<Option<VirtualKeyCode>>,
+    mut state: ResMut<State<AppState>>,
+    status: Res<CourseStatus>,
+    mut setup: ResMut<Setup>,
+    mut course: ResMut<Course>,
+    mut balls: Query<&mut Ball>,
+    players: Query<bevy::Entity, With<Player>>,
+    clubs: Res<ClubSet>,
+    window: Res<Window>,
+    commands: &mut Commands,
+) {
+    let mut batch = DrawBatch::new();
+    batch.draw_box(
+        Rect::with_size(20, 20, 40, 13),
+        ColorPair::new(WHITE, BLACK),
+    );
+    match &*status {
+        CourseStatus::Ready(name) => {
+            batch
+                .print(Point::new(30, 22), "Menu! D to play!")
+                .print(Point::new(28, 23), "E to edit the hole")
+                .print(Point::new(22, 24), name)
+                .print(
+                    Point::new(22, 25),
+                    format!("< Tees: {} >", setup.tees().unwrap_or("Regular")),
+                )
+                .print(
+                    Point::new(22, 26),
+                    format!("^ Pins: {} v", setup.pin_sheet()),
+                )
+                .print(Point::new(22, 27), format!("R: {}", course.round().name()))
+                .print(
+                    Point::new(22, 28),
+                    format!("H: Start at hole {}", course.hole_number()),
+                )
+                .print(
+                    Point::new(22, 29),
+                    match setup.players {
+                        1 => "P: 1 player".to_string(),
+                        n => format!("P: {} players", n),
+                    },
+                )
+                .print(Point::new(22, 30), format!("G: {}", setup.format.name()))
+                .print(
+                    Point::new(22, 31),
+                    format!(
+                        "1-{}: Handicaps {}",
+                        setup.players,
+                        setup.handicaps[..setup.players].iter().join(" ")
+                    ),
+                )
+                .print(
+                    Point::new(22, 32),
+                    format!("C: {} played by the computer", setup.computers),
+                );
+        }
+        CourseStatus::Failed(error) => {
+            batch.print_color(
+                Point::new(22, 21),
+                "Could not load the course:",
+                ColorPair::new(RED, BLACK),
+            );
+            let message: Vec<char> = error.to_string().chars().collect();
+            for (i, line) in message.chunks(37).take(4).enumerate() {
+                batch.print(
+                    Point::new(22, 22 + i as i32),
+                    line.iter().collect::<String>(),
+                );
             }
         }
-        _ => {}
+    }
+    batch.submit(15050).expect("Box error");
+    let ready = matches!(*status, CourseStatus::Ready(_));
+    let next_state = match (ready, *key) {
+        (true, Some(VirtualKeyCode::D)) => Some(AppState::Playing),
+        (true, Some(VirtualKeyCode::E)) => Some(AppState::Editor),
+        (true, Some(VirtualKeyCode::Left)) => {
+            setup.previous_tees();
+            None
+        }
+        (true, Some(VirtualKeyCode::Right)) => {
+            setup.next_tees();
+            None
+        }
+        (true, Some(VirtualKeyCode::Up)) => {
+            setup.next_pins();
+            None
+        }
+        (true, Some(VirtualKeyCode::Down)) => {
+            setup.previous_pins();
+            None
+        }
+        (true, Some(VirtualKeyCode::R)) => {
+            course.next_round();
+            None
+        }
+        (true, Some(VirtualKeyCode::H)) => {
+            course.next_start();
+            None
+        }
+        (true, Some(VirtualKeyCode::P)) => {
+            setup.next_players();
+            None
+        }
+        (true, Some(VirtualKeyCode::C)) => {
+            setup.next_computers();
+            None
+        }
+        (true, Some(VirtualKeyCode::G)) => {
+            setup.next_format();
+            None
+        }
+        (true, Some(VirtualKeyCode::Key1)) => {
+            setup.raise_handicap(0);
+            None
+        }
+        (true, Some(VirtualKeyCode::Key2)) if setup.players > 1 => {
+            setup.raise_handicap(1);
+            None
+        }
+        (true, Some(VirtualKeyCode::Key3)) if setup.players > 2 => {
+            setup.raise_handicap(2);
+            None
+        }
+        (true, Some(VirtualKeyCode::Key4)) if setup.players > 3 => {
+            setup.raise_handicap(3);
+            None
+        }
+        _ => None,
     };
+    if let Some(next_state) = next_state {
+        // set out the chosen first hole, for play or for editing
+        if let Some(hole) = course.current() {
+            // a fresh group for every round; the tee-off puts them on the tee
+            for entity in players.iter() {
+                commands.despawn(entity);
+            }
+            Player::spawn_all(
+                commands,
+                setup.players,
+                setup.computers,
+                &hole.map.tee,
+                &clubs,
+            );
+            hole_handler::set_out(commands, hole, &setup, &mut balls, &window);
+            commands.insert_resource(Scorecard::new());
+            commands.insert_resource(setup.scoring(course.stroke_indexes()));
+        }
+        if state.set_next(next_state).is_err() {
+            panic!("Could not leave the menu!")
+        }
+    }
 }
@@ -1,6 +1,10 @@
 pub mod ball_render;
+pub mod computer_player;
+pub mod editor_system;
 pub mod hole_handler;
 pub mod map_render;
 pub mod menu_system;
+pub mod scorecard_render;
 pub mod turn_handler;
+pub mod turn_order;
 pub mod ui_render;
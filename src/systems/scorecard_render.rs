@@ -0,0 +1,191 @@
+use crate::prelude::*;
+
+const CARD_X: i32 = 10;
+const CARD_Y: i32 = 8;
+const CARD_WIDTH: i32 = 60;
+
+/** The card so far, over the green once the last player has holed out. **/
+pub fn scorecard_overlay(
+    turn_stage: Res<TurnStage>,
+    hole_state: Res<HoleState>,
+    scorecard: Res<Scorecard>,
+    players: Query<(&Player, Option<&Active>)>,
+) {
+    let everyone_in = players
+        .iter()
+        .all(|(player, active)| active.is_some() || player.holed());
+    if let (TurnStage::Finished, HoleState::Holed(_, _), true) =
+        (*turn_stage, *hole_state, everyone_in)
+    {
+        let mut batch = DrawBatch::new();
+        let bottom = draw_card(&mut batch, &scorecard, 0);
+        batch.print(
+            Point::new(CARD_X + 2, bottom),
+            "Press Space for the next hole",
+        );
+        batch.submit(30000).expect("Scorecard error");
+    }
+}
+
+pub fn round_summary(
+    key: Res<Option<VirtualKeyCode>>,
+    mut state: ResMut<State<AppState>>,
+    scorecard: Res<Scorecard>,
+    scoring: Res<Scoring>,
+    mut course: ResMut<Course>,
+    setup: Res<Setup>,
+    mut balls: Query<&mut Ball>,
+    window: Res<Window>,
+    commands: &mut Commands,
+) {
+    let mut batch = DrawBatch::new();
+    let golfers = scorecard.players();
+    let results = scoring.0.results();
+    let below = golfers.len() + results.len();
+    let bottom = draw_card(&mut batch, &scorecard, below as i32 + 4);
+    batch.print_color(
+        Point::new(CARD_X + 2, bottom),
+        format!("Round complete! {}", scoring.0.name()),
+        ColorPair::new(YELLOW, BLACK),
+    );
+    for (i, &player) in golfers.iter().enumerate() {
+        let tally = [
+            ("Birdies+", scorecard.count(player, |diff| diff < 0)),
+            ("Pars", scorecard.count(player, |diff| diff == 0)),
+            ("Bogeys", scorecard.count(player, |diff| diff == 1)),
+            ("Worse", scorecard.count(player, |diff| diff > 1)),
+        ];
+        batch.print(
+            Point::new(CARD_X + 2, bottom + 1 + i as i32),
+            format!(
+                "P{} {:>3} ({})  {}",
+                player + 1,
+                scorecard.strokes(player),
+                Scorecard::relative(scorecard.to_par(player)),
+                tally
+                    .iter()
+                    .map(|(label, n)| format!("{} {}", label, n))
+                    .join("  ")
+            ),
+        );
+    }
+    for (i, line) in results.iter().enumerate() {
+        batch.print_color(
+            Point::new(CARD_X + 2, bottom + 1 + (golfers.len() + i) as i32),
+            line,
+            ColorPair::new(YELLOW, BLACK),
+        );
+    }
+    batch.print(
+        Point::new(CARD_X + 2, bottom + below as i32 + 2),
+        "R to play the round again, M to return to the menu",
+    );
+    batch.submit(30000).expect("Summary error");
+
+    match *key {
+        Some(VirtualKeyCode::M) => {
+            course.restart();
+            commands.insert_resource(Scorecard::new());
+            if state.set_next(AppState::Menu).is_err() {
+                panic!("Could not return to the menu!")
+            }
+        }
+        Some(VirtualKeyCode::R) => {
+            course.restart();
+            if let Some(hole) = course.current() {
+                hole_handler::set_out(commands, hole, &setup, &mut balls, &window);
+            }
+            commands.insert_resource(Scorecard::new());
+            commands.insert_resource(setup.scoring(course.stroke_indexes()));
+            if state.set_next(AppState::Playing).is_err() {
+                panic!("Could not restart the round!")
+            }
+        }
+        _ => {}
+    }
+}
+
+/** Draw the card with `extra` blank rows under it; returns the first of them.
+ * A lone golfer gets the name of each score; a group gets a column each. **/
+fn draw_card(batch: &mut DrawBatch, scorecard: &Scorecard, extra: i32) -> i32 {
+    let golfers = scorecard.players();
+    let rows = scorecard.holes().count() as i32;
+    let column = |i: usize| CARD_X + 29 + 6 * i as i32;
+    batch.draw_box(
+        Rect::with_size(CARD_X, CARD_Y, CARD_WIDTH, rows + 6 + extra),
+        ColorPair::new(WHITE, BLACK),
+    );
+    batch.print_color(
+        Point::new(CARD_X + 2, CARD_Y + 1),
+        format!("{:>4}  {:<16} {:>3}", "Hole", "", "Par"),
+        ColorPair::new(GRAY, BLACK),
+    );
+    for (i, player) in golfers.iter().enumerate() {
+        let heading = match golfers.len() {
+            1 => "Score".to_string(),
+            _ => format!("P{}", player + 1),
+        };
+        batch.print_color(
+            Point::new(column(i), CARD_Y + 1),
+            format!("{:>5}", heading),
+            ColorPair::new(GRAY, BLACK),
+        );
+    }
+    for (row, hole) in scorecard.holes().enumerate() {
+        let y = CARD_Y + 2 + row as i32;
+        batch.print(
+            Point::new(CARD_X + 2, y),
+            format!(
+                "{:>4}  {:<16} {:>3}",
+                hole.number,
+                hole.name.chars().take(16).collect::<String>(),
+                hole.par
+            ),
+        );
+        for (i, &player) in golfers.iter().enumerate() {
+            let score = match scorecard.score(player, hole.number) {
+                Some(score) => score,
+                None => continue,
+            };
+            let color = match score.to_par() {
+                diff if diff < 0 => RED,
+                0 => WHITE,
+                _ => GRAY,
+            };
+            let text = if golfers.len() == 1 {
+                format!("{:>5}  {}", score.strokes, score.describe())
+            } else {
+                format!("{:>5}", score.strokes)
+            };
+            batch.print_color(Point::new(column(i), y), text, ColorPair::new(color, BLACK));
+        }
+    }
+    let y = CARD_Y + 3 + rows;
+    batch.print_color(
+        Point::new(CARD_X + 2, y),
+        format!(
+            "{:>4}  {:<16} {:>3}",
+            "",
+            "Total",
+            golfers.first().map_or(0, |&p| scorecard.par(p))
+        ),
+        ColorPair::new(YELLOW, BLACK),
+    );
+    for (i, &player) in golfers.iter().enumerate() {
+        let total = if golfers.len() == 1 {
+            format!(
+                "{:>5}  {}",
+                scorecard.strokes(player),
+                Scorecard::relative(scorecard.to_par(player))
+            )
+        } else {
+            format!("{:>5}", scorecard.strokes(player))
+        };
+        batch.print_color(
+            Point::new(column(i), y),
+            total,
+            ColorPair::new(YELLOW, BLACK),
+        );
+    }
+    CARD_Y + 5 + rows
+}
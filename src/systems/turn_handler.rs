@@ -5,56 +5,157 @@ pub fn turn_handler(
     key: Res<Option<VirtualKeyCode>>,
     mut camera: ResMut<Camera>,
     mut turn_stage: ResMut<TurnStage>,
-    mut balls: Query<&mut Ball>,
+    mut balls: Query<&mut Ball, With<Active>>,
+    players: Query<&Player, With<Active>>,
+    humans: Query<&Player, Without<Computer>>,
     mut hole_state: ResMut<HoleState>,
+    hole: Res<Hole>,
+    mut wind: ResMut<Wind>,
+    mut notice: ResMut<Notice>,
+    mut scorecard: ResMut<Scorecard>,
+    scoring: Res<Scoring>,
 ) {
+    wind.tick(dt.seconds());
+    notice.tick(dt.seconds());
+    let player = match players.iter().next() {
+        Some(player) => player,
+        None => return,
+    };
+    let clubs = &player.clubs;
+    let lie = balls
+        .iter_mut()
+        .map(|b| b.tile_position())
+        .find(|pos| hole.map.in_bounds(pos))
+        .map_or(Surface::DEEP_ROUGH, |pos| hole.map.tile_at(&pos).surface());
+    let humans: Vec<usize> = humans.iter().map(|p| p.number).collect();
+    let gimme = scoring.0.concessions()
+        && Match::may_concede(player.number, &humans)
+        && balls.iter_mut().any(|b| Match::gimme(&hole.map, &b));
     let updated_stage: TurnStage = match *turn_stage {
-        TurnStage::ClubSelection(clubs, current) => match *key {
-            Some(VirtualKeyCode::Down) => TurnStage::ClubSelection(clubs, clubs.next_club(current)),
-            Some(VirtualKeyCode::Up) => {
-                TurnStage::ClubSelection(clubs, clubs.previous_club(current))
+        TurnStage::ClubSelection(current) => match *key {
+            Some(VirtualKeyCode::Down) => TurnStage::ClubSelection(clubs.next_club(current)),
+            Some(VirtualKeyCode::Up) => TurnStage::ClubSelection(clubs.previous_club(current)),
+            Some(VirtualKeyCode::C) if gimme => {
+                // the other side gives the putt; it still counts a stroke
+                hole_state.concede();
+                balls.iter_mut().for_each(|mut b| b.move_to(&hole.map.flag));
+                if let HoleState::Holed(strokes, _) = *hole_state {
+                    scorecard.record(player.number, &hole, strokes);
+                }
+                TurnStage::Finished
             }
-            _ => TurnStage::ClubSelection(clubs, current),
+            _ => TurnStage::ClubSelection(current),
         },
         TurnStage::Aiming(aim, club) => {
             let new_aim = aim.adjust(*key);
             TurnStage::Aiming(new_aim, club)
         }
         TurnStage::Swinging(swing, aim, club) => {
-            let new_swing = handle_swing(swing.clone()).unwrap_or(swing);
+            let new_swing = handle_swing(swing, lie.stimp.is_some()).unwrap_or(swing);
             TurnStage::Swinging(new_swing, aim, club)
         }
         TurnStage::Traveling(mut travel) => {
-            let s = dt.seconds() * 2.;
+            // a fixed step a frame, as the planner plays shots out
+            let s = Travel::STEP;
             let dx = travel.tile_distance(s);
-            println!("Frame advances {:?} ms", dt.t_ms);
-            println!("Ball moves: {:?}", dx);
+            let dz = travel.lateral_distance(s);
+            let mut under = None;
+            let mut cup_distance = f32::MAX;
+            let mut crossed = Vec::new();
             balls.iter_mut().for_each(|mut b| {
+                let from = *b;
                 b.mv(travel.direction, dx);
-                camera.update(b.tile_position());
+                b.mv(travel.direction - 90., dz);
+                cup_distance = b.closest_approach(&from, &hole.map.flag);
+                let pos = b.tile_position();
+                crossed = hole
+                    .map
+                    .crossed(&from.tile_position(), &pos, &b.lie_position());
+                under = if hole.map.in_bounds(&pos) {
+                    let ground = hole.map.ground_at(&pos, &b.lie_position());
+                    Some((hole.map.tile_at(&pos), ground))
+                } else {
+                    None
+                };
+                camera.update(pos);
             });
-            travel.tick(s);
+            // a fast ball can cross several tiles in a frame; a tree on any of them may stop it
+            for (tile, ground) in crossed {
+                if travel.pass_over(tile.obstacle_height(), &ground) {
+                    notice.show(&format!("Into the {}!", tile.name().to_lowercase()));
+                    break;
+                }
+            }
+            if let Some((_, ground)) = under {
+                travel.tick(s, &ground, &wind);
+            }
+            match (under.map(|(tile, _)| tile), travel.on_ground()) {
+                (None, _) | (Some(MapTile::OutOfBounds), true) => {
+                    // stroke and distance: the shot counts, plus one penalty stroke
+                    hole_state.penalty(1);
+                    notice.show("Out of bounds! +1 stroke, replay from last lie");
+                    travel.stop();
+                    balls.iter_mut().for_each(|mut b| {
+                        b.replay();
+                        camera.update(b.tile_position());
+                    });
+                }
+                (Some(MapTile::Water), true) => {
+                    hole_state.penalty(1);
+                    notice.show("In the water! +1 stroke, take a drop");
+                    travel.stop();
+                    balls.iter_mut().for_each(|mut b| {
+                        let drop = hole.map.drop_point(&b.lie_position(), &b.tile_position());
+                        b.move_to(&drop);
+                        camera.update(b.tile_position());
+                    });
+                }
+                _ => {
+                    if cup_distance <= Travel::CUP_RADIUS && travel.reach_cup() == Cup::Drop {
+                        let near_green = balls
+                            .iter_mut()
+                            .any(|b| hole.map.near_green(&b.lie_position(), HoleOut::CHIP_REACH));
+                        balls.iter_mut().for_each(|mut b| b.move_to(&hole.map.flag));
+                        hole_state.hole_out(travel.putt, near_green);
+                        if let HoleState::Holed(strokes, _) = *hole_state {
+                            scorecard.record(player.number, &hole, strokes);
+                        }
+                    }
+                }
+            }
             TurnStage::Traveling(travel)
         }
         stage => stage,
     };
     let new_stage = match (updated_stage, *key) {
+        (TurnStage::Swinging(Swing::Accuracy(pow, acc), aim, club), key)
+            if key == Some(VirtualKeyCode::Space) || acc <= Swing::ACCURACY_FLOOR =>
+        {
+            hole_state.increment();
+            balls.iter_mut().for_each(|mut b| b.strike());
+            Some(TurnStage::Traveling(Travel::new(
+                &pow,
+                &acc,
+                &aim,
+                clubs.at(&club),
+                &lie,
+            )))
+        }
         (TurnStage::Swinging(swing, aim, club), Some(VirtualKeyCode::Space)) => match swing {
             Swing::Start => Some(TurnStage::Swinging(Swing::Power(0.), aim, club)),
-            Swing::Power(pow) => Some(TurnStage::Swinging(Swing::Accuracy(pow, 1.0), aim, club)),
-            Swing::Accuracy(pow, _acc) => {
-                hole_state.increment();
-                Some(TurnStage::Traveling(Travel::new(&pow, &aim, &club)))
-            }
+            Swing::Power(pow) => Some(TurnStage::Swinging(Swing::Accuracy(pow, pow), aim, club)),
+            Swing::Accuracy(_, _) => None,
         },
         (TurnStage::Traveling(travel), _) => {
             if travel.finished() {
-                Some(turn_stage.next())
+                Some(turn_stage.next(clubs, &lie))
             } else {
                 None
             }
         }
-        (stage, Some(VirtualKeyCode::Space)) => Some(stage.next()),
+        // who plays next is up to `turn_order`
+        (TurnStage::Finished, _) => None,
+        (stage, Some(VirtualKeyCode::Space)) => Some(stage.next(clubs, &lie)),
         _ => None,
     };
     match new_stage {
@@ -63,20 +164,25 @@ pub fn turn_handler(
     }
 }
 
-fn handle_swing(swing: Swing) -> Option<Swing> {
+fn handle_swing(swing: Swing, putting: bool) -> Option<Swing> {
     match swing {
         Swing::Power(power) => {
-            let new_power = if power < 100. as f32 {
-                power + 1.
+            let rate = if putting {
+                Swing::PUTT_POWER_RATE
             } else {
-                power
+                Swing::POWER_RATE
             };
+            let new_power = if power < 100. { power + rate } else { power };
             if new_power >= 100. {
-                Some(Swing::Accuracy(new_power, 0.))
+                Some(Swing::Accuracy(new_power, new_power))
             } else {
                 Some(Swing::Power(new_power))
             }
         }
+        Swing::Accuracy(power, accuracy) => {
+            let cursor = (accuracy - Swing::RETURN_RATE).max(Swing::ACCURACY_FLOOR);
+            Some(Swing::Accuracy(power, cursor))
+        }
         _ => None,
     }
 }
@@ -0,0 +1,61 @@
+use crate::prelude::*;
+
+/** Once a shot is finished, hand the turn to whoever is farthest from the
+ * hole and has not holed out; on the tee, the player with the honor. **/
+pub fn turn_order(
+    key: Res<Option<VirtualKeyCode>>,
+    mut turn_stage: ResMut<TurnStage>,
+    mut hole_state: ResMut<HoleState>,
+    hole: Res<Hole>,
+    mut camera: ResMut<Camera>,
+    mut notice: ResMut<Notice>,
+    mut players: Query<(bevy::Entity, &mut Player, &Ball, Option<&Active>)>,
+    commands: &mut Commands,
+) {
+    let finished = matches!(*turn_stage, TurnStage::Finished);
+    if !finished || *key != Some(VirtualKeyCode::Space) {
+        return;
+    }
+    let mut current = None;
+    for (entity, mut player, _, active) in players.iter_mut() {
+        if active.is_some() {
+            player.hole_state = *hole_state;
+            current = Some(entity);
+        }
+    }
+    let flag = hole.map.flag;
+    let away = |ball: &Ball| DistanceAlg::Pythagoras.distance2d(ball.tile_position(), flag);
+    let next = players
+        .iter_mut()
+        .filter(|(_, player, _, _)| !player.holed())
+        .max_by(|(_, a, a_ball, _), (_, b, b_ball, _)| {
+            away(a_ball)
+                .partial_cmp(&away(b_ball))
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.honor.cmp(&a.honor))
+        })
+        .map(|(entity, _, _, _)| entity);
+    // with everyone holed out, `hole_transition` moves on to the next hole
+    let next = match next {
+        Some(next) => next,
+        None => return,
+    };
+    if let Ok((_, player, ball, _)) = players.get_mut(next) {
+        if current != Some(next) {
+            if let Some(current) = current {
+                commands.remove_one::<Active>(current);
+            }
+            commands.insert_one(next, Active);
+            notice.show(&format!("{} to play", player.name));
+        }
+        let pos = ball.tile_position();
+        let lie = if hole.map.in_bounds(&pos) {
+            hole.map.tile_at(&pos).surface()
+        } else {
+            Surface::DEEP_ROUGH
+        };
+        camera.update(pos);
+        *hole_state = player.hole_state;
+        *turn_stage = TurnStage::ClubSelection(player.clubs.default_for(&lie));
+    }
+}
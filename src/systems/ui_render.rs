@@ -1,14 +1,28 @@
 use crate::prelude::*;
 
+const METER_X: i32 = 10;
+const METER_WIDTH: i32 = 51;
+
 pub fn render_ui(
     turn_stage: Res<TurnStage>,
     hole: Res<Hole>,
     camera: Res<Camera>,
     _key: Res<Option<VirtualKeyCode>>,
-    balls: Query<&Ball>,
+    balls: Query<&Ball, With<Active>>,
+    players: Query<&Player, With<Active>>,
+    waiting: Query<&Player, Without<Active>>,
+    humans: Query<&Player, Without<Computer>>,
     window: Res<Window>,
     hole_state: Res<HoleState>,
+    wind: Res<Wind>,
+    notice: Res<Notice>,
+    scoring: Res<Scoring>,
 ) {
+    let player = match players.iter().next() {
+        Some(player) => player,
+        None => return,
+    };
+    let clubs = &player.clubs;
     let mut ctx = DrawBatch::new();
     let map = &hole.map;
 
@@ -48,19 +62,14 @@ pub fn render_ui(
                 let arr = map.intersection(ball.tile_position(), coord);
                 ctx.set(arr, ColorPair::new(RED, BLACK), 30);
             }
-            ctx.bar_horizontal(
-                Point::new(2, window.height - 10),
-                51,
-                pow as i32,
-                100,
-                ColorPair::new(RED, BLACK),
-            );
+            render_meter(&mut ctx, &window, pow, None);
         }
-        Swing::Accuracy(_, _) => {
+        Swing::Accuracy(pow, acc) => {
             ctx.print(
                 Point::new(2, window.height - 3),
-                "[Acc] Aim, Press Space to Start Swing!",
+                "[Acc] Press Space at the mark!",
             );
+            render_meter(&mut ctx, &window, pow, Some(acc));
             let coord = crosshair_coord(ball.tile_position(), direction);
             if map.in_bounds(&coord) {
                 let bg = map.bg(&coord);
@@ -78,15 +87,31 @@ pub fn render_ui(
     let instr = match *hole_state {
         HoleState::TeeOff => "Start The Hole!".to_string(),
         HoleState::Stroke(strokes) => format!("Strokes: {}", strokes),
-        HoleState::Holed => "Hole finished!".to_string(),
+        HoleState::Holed(strokes, out) => format!("{} ({})", out.describe(), strokes),
+    };
+    let lie = balls
+        .iter()
+        .map(|b| b.tile_position())
+        .find(|pos| map.in_bounds(pos))
+        .map(|pos| map.tile_at(&pos));
+    let green_speed = match lie.and_then(|tile| tile.surface().stimp) {
+        Some(stimp) => format!("Stimp: {:.0}", stimp),
+        None => String::new(),
     };
     match *turn_stage {
-        TurnStage::ClubSelection(clubs, current) => {
+        TurnStage::ClubSelection(current) => {
             let club = clubs.at(&current);
-            println!("Current club: {:?} = {:?}", &current, club.name);
+            let humans: Vec<usize> = humans.iter().map(|p| p.number).collect();
+            let concede = scoring.0.concessions()
+                && Match::may_concede(player.number, &humans)
+                && balls.iter().any(|ball| Match::gimme(map, ball));
             ctx.print(
                 Point::new(2, window.height - 3),
-                format!("Club selected: {}", club.name),
+                format!(
+                    "Club selected: {}{}",
+                    club.name,
+                    if concede { "  (C to concede)" } else { "" }
+                ),
             );
         }
         TurnStage::Aiming(Aim { degrees }, _) => {
@@ -108,17 +133,87 @@ pub fn render_ui(
         }
         TurnStage::Swinging(swing, aim, _) => {
             for ball in balls.iter() {
-                render_swing(ball, swing.clone(), &aim.degrees);
+                render_swing(ball, swing, &aim.degrees);
             }
         }
-        TurnStage::Traveling(_travel) => {
+        TurnStage::Traveling(travel) => {
+            let status = if travel.lipped_out {
+                "Lipped out!"
+            } else {
+                "Traveling!"
+            };
             for _ball in balls.iter() {
-                ctx.print(Point::new(2, window.height - 3), "Traveling!");
+                ctx.print(Point::new(2, window.height - 3), status);
             }
         }
-        TurnStage::Finished => {
-            ctx.print(Point::new(2, window.height - 3), "Finishing Turn");
-        }
+        TurnStage::Finished => match *hole_state {
+            HoleState::Holed(_, out) if waiting.iter().all(|p| p.holed()) => {
+                ctx.print(
+                    Point::new(2, window.height - 3),
+                    format!("{} Press Space for the next hole", out.describe()),
+                );
+            }
+            HoleState::Holed(_, out) => {
+                ctx.print(
+                    Point::new(2, window.height - 3),
+                    format!("{} Press Space for the next player", out.describe()),
+                );
+            }
+            _ => {
+                ctx.print(Point::new(2, window.height - 3), "Finishing Turn");
+            }
+        },
+    }
+
+    if let Some(message) = notice.message() {
+        ctx.print_color(
+            Point::new(2, window.height - 5),
+            message,
+            ColorPair::new(YELLOW, BLACK),
+        );
+    }
+
+    let selected = match *turn_stage {
+        TurnStage::ClubSelection(club) => Some(club),
+        TurnStage::Aiming(_, club) => Some(club),
+        TurnStage::Swinging(_, _, club) => Some(club),
+        _ => None,
+    };
+    for (i, club) in clubs.iter().enumerate() {
+        let color = if selected == Some(i) { YELLOW } else { GRAY };
+        ctx.print_color(
+            Point::new(camera.width() + 2, 9 + i as i32),
+            &club.short_name,
+            ColorPair::new(color, BLACK),
+        );
+    }
+
+    let info_x = camera.width() + 1;
+    let info_y = 10 + clubs.iter().count() as i32;
+    ctx.print(Point::new(info_x, info_y), format!("Hole {}", hole.number));
+    ctx.print(
+        Point::new(info_x, info_y + 1),
+        hole.name.chars().take(13).collect::<String>(),
+    );
+    ctx.print(Point::new(info_x, info_y + 2), format!("Par {}", hole.par));
+    ctx.print(
+        Point::new(info_x, info_y + 3),
+        format!("{} yds", hole.yards),
+    );
+    ctx.print(
+        Point::new(info_x, info_y + 4),
+        format!("Hcp {}", hole.handicap),
+    );
+    if let Some(tees) = &hole.tee_name {
+        ctx.print(Point::new(info_x, info_y + 5), format!("{} tees", tees));
+    }
+    if let Some((leader, margin)) = scoring.0.status() {
+        ctx.print_color(
+            Point::new(info_x, info_y + 7),
+            leader,
+            ColorPair::new(YELLOW, BLACK),
+        );
+        ctx.print(Point::new(info_x, info_y + 8), margin);
     }
 
     let (ui_h0, ui_h2) = (camera.height() - 1, window.height as i32 - 1);
@@ -136,17 +231,72 @@ pub fn render_ui(
             Rect::with_exact(camera.width(), 0, ui_w as i32, camera.height() - 1),
             ColorPair::new(WHITE, BLACK),
         )
+        .print_color(
+            Point::new(camera.width() + 1, 1),
+            &player.name,
+            ColorPair::new(player.color(), BLACK),
+        )
         .print(Point::new(camera.width() + 1, 2), instr)
+        .print(
+            Point::new(camera.width() + 1, 4),
+            format!("Lie: {}", lie.map_or("", |tile| tile.name())),
+        )
+        .print(Point::new(camera.width() + 1, 5), green_speed)
+        .print(
+            Point::new(camera.width() + 1, 6),
+            format!("Wind: {:.0} m/s", wind.current_speed()),
+        )
+        .print(
+            Point::new(camera.width() + 1, 7),
+            format!("Toward: {}", wind.compass()),
+        )
         .submit(1010)
         .expect("Box error");
     ctx.submit(20220).expect("UI Error!");
 }
 
+/** Swing meter: power fills rightward from the sweet spot, the accuracy
+ * cursor sweeps back past it into the miss zone on the left. **/
+fn render_meter(ctx: &mut DrawBatch, window: &Window, power: f32, accuracy: Option<f32>) {
+    let y = window.height as i32 - 10;
+    let origin = METER_X + meter_offset(Swing::ACCURACY_FLOOR);
+    for x in origin..METER_X {
+        ctx.set(
+            Point::new(x, y),
+            ColorPair::new(DARKGRAY, BLACK),
+            to_cp437('░'),
+        );
+    }
+    ctx.bar_horizontal(
+        Point::new(METER_X, y),
+        METER_WIDTH,
+        power as i32,
+        100,
+        ColorPair::new(RED, BLACK),
+    );
+    ctx.set(
+        Point::new(METER_X, y - 1),
+        ColorPair::new(YELLOW, BLACK),
+        to_cp437('▼'),
+    );
+    if let Some(acc) = accuracy {
+        ctx.set(
+            Point::new(METER_X + meter_offset(acc), y + 1),
+            ColorPair::new(WHITE, BLACK),
+            to_cp437('▲'),
+        );
+    }
+}
+
+fn meter_offset(points: f32) -> i32 {
+    (points * (METER_WIDTH - 1) as f32 / 100.).round() as i32
+}
+
 fn crosshair_coord(origin: Point, degrees: &f32) -> Point {
     let radius = 20.;
     let rads = (*degrees + 90.).to_radians();
     let dx = rads.cos() * radius;
-    let dy = -1. * rads.sin() * radius;
+    let dy = -rads.sin() * radius;
     let dir = Point::new(dx.round() as i32, dy.round() as i32);
     origin + dir
 }
@@ -0,0 +1,159 @@
+use crate::prelude::*;
+use std::fs;
+use std::path::Path;
+
+/** Check a map grid, or every hole of a `.toml` course file, and print what
+ * is wrong with it. Returns whether it is fit to play. **/
+pub fn validate(path: &str) -> bool {
+    let clubs = match ClubSet::load(ClubSet::DEFAULT_FILE) {
+        Ok(clubs) => clubs,
+        Err(error) => {
+            println!("{}: {}", ClubSet::DEFAULT_FILE, error);
+            return false;
+        }
+    };
+    if path.ends_with(".toml") {
+        return validate_course(path, &clubs);
+    }
+    match Map::read_grid(path) {
+        Ok((lines, heights)) => {
+            match validate_grid(path, &lines, heights.as_deref(), &Smoothing::default()) {
+                Some(map) => check_reach(path, &map, &clubs),
+                None => false,
+            }
+        }
+        Err(error) => {
+            println!("{}: {}", path, error);
+            false
+        }
+    }
+}
+
+fn validate_course(path: &str, clubs: &ClubSet) -> bool {
+    let manifest: CourseManifest = match fs::read_to_string(path)
+        .map_err(CourseError::Io)
+        .and_then(|text| toml::from_str(&text).map_err(CourseError::Manifest))
+    {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            println!("{}: {}", path, error);
+            return false;
+        }
+    };
+    if manifest.holes.is_empty() {
+        println!("{}: {}", path, CourseError::NoHoles);
+        return false;
+    }
+    let folder = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+    let mut clean = true;
+    for (i, hole) in manifest.holes.iter().enumerate() {
+        let label = format!("{} hole {} ({})", path, i + 1, hole.name);
+        let map = match hole.grid_lines(folder) {
+            Some(Ok((lines, heights))) => {
+                validate_grid(&label, &lines, heights.as_deref(), &manifest.smoothing)
+            }
+            Some(Err(error)) => {
+                println!("{}: {}", label, error);
+                None
+            }
+            None => {
+                println!("{}: needs a map or a grid", label);
+                None
+            }
+        };
+        clean &= match map {
+            Some(map) => check_markers(&label, map, hole, clubs),
+            None => false,
+        };
+    }
+    clean
+}
+
+/** Print the grid's problems, and how smoothing changed it; the map as
+ * played when the grid is sound. **/
+fn validate_grid(
+    label: &str,
+    lines: &[String],
+    heights: Option<&[String]>,
+    smoothing: &Smoothing,
+) -> Option<Map> {
+    let problems = Map::problems(lines);
+    for problem in problems.iter() {
+        println!("{}: {}", label, problem);
+    }
+    if !problems.is_empty() {
+        return None;
+    }
+    let written = Map::parse(lines, heights).ok()?;
+    let mut smoothed = written.clone();
+    smoothed.smooth_rough(smoothing);
+    let (before, after) = (written.to_lines(), smoothed.to_lines());
+    let changed: Vec<char> = before
+        .iter()
+        .zip(after.iter())
+        .flat_map(|(b, a)| b.chars().zip(a.chars()))
+        .filter(|(b, a)| b != a)
+        .map(|(_, a)| a)
+        .collect();
+    if !changed.is_empty() {
+        let to = |tile: MapTile| changed.iter().filter(|c| **c == tile.to_char()).count();
+        println!(
+            "{}: smoothing cut {} deep rough tiles (.) to first cut (:) and {} to rough (,):",
+            label,
+            to(MapTile::FirstCut),
+            to(MapTile::Rough)
+        );
+        for line in after.iter() {
+            println!("    {}", line);
+        }
+    }
+    Some(smoothed)
+}
+
+/** Every named tee has to be on the map and reach every named pin. **/
+fn check_markers(label: &str, mut map: Map, hole: &HoleManifest, clubs: &ClubSet) -> bool {
+    let to_point = |m: &Marker| (m.name.clone(), Point::new(m.x, m.y));
+    let mut tees: Vec<(String, Point)> = hole.tees.iter().map(to_point).collect();
+    let mut pins: Vec<(String, Point)> = hole.pins.iter().map(to_point).collect();
+    if tees.is_empty() {
+        tees.push(("T".to_string(), map.tee));
+    }
+    if pins.is_empty() {
+        pins.push(("F".to_string(), map.flag));
+    }
+    let mut clean = true;
+    for (name, point) in tees.iter().chain(pins.iter()) {
+        if !map.in_bounds(point) {
+            println!(
+                "{}: {} at ({}, {}) is off the map",
+                label, name, point.x, point.y
+            );
+            clean = false;
+        }
+    }
+    if !clean {
+        return false;
+    }
+    for (tee_name, tee) in tees.iter() {
+        for (pin_name, pin) in pins.iter() {
+            map.tee = *tee;
+            map.flag = *pin;
+            let marked = format!("{} [{} tee, {} pin]", label, tee_name, pin_name);
+            clean &= check_reach(&marked, &map, clubs);
+        }
+    }
+    clean
+}
+
+fn check_reach(label: &str, map: &Map, clubs: &ClubSet) -> bool {
+    if map.flag_reachable(clubs) {
+        println!("{}: ok", label);
+        true
+    } else {
+        println!(
+            "{}: flag at ({}, {}) can't be reached from the tee",
+            label, map.flag.x, map.flag.y
+        );
+        false
+    }
+}